@@ -0,0 +1,38 @@
+use std::process::ExitCode;
+
+use solve::Registry;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: solve::alloc::CountingAllocator = solve::alloc::CountingAllocator;
+
+fn main() -> ExitCode {
+    let registry = [
+        day_1::day(),
+        day_2::day(),
+        day_3::day(),
+        day_4::day(),
+        day_5::day(),
+        day_6::day(),
+        day_7::day(),
+        day_8::day(),
+        day_9::day(),
+        day_10::day(),
+        day_11::day(),
+        day_12::day(),
+        day_13::day(),
+        day_14::day(),
+        day_15::day(),
+        day_16::day(),
+        day_17::day(),
+        day_18::day(),
+        day_19::day(),
+        day_20::day(),
+        day_21::day(),
+        day_22::day(),
+    ]
+    .into_iter()
+    .collect::<Registry>();
+
+    solve::runner::main(&registry)
+}
@@ -0,0 +1,33 @@
+use anyhow::{Context, Result};
+use solve::Grouped;
+
+fn part_one(input: &Grouped<i32>) -> Result<i32> {
+    input
+        .groups
+        .iter()
+        .map(|g| g.iter().sum())
+        .max()
+        .context("no elves to count calories for")
+}
+
+fn part_two(input: &Grouped<i32>) -> i32 {
+    let mut totals = input
+        .groups
+        .iter()
+        .map(|g| g.iter().sum())
+        .collect::<Vec<_>>();
+    totals.sort();
+    totals.iter().rev().take(3).sum()
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(1, part_one, part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
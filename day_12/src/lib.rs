@@ -0,0 +1,135 @@
+use std::{collections::HashSet, io::BufRead};
+
+use anyhow::{anyhow, Context, Result};
+use solve::{
+    search,
+    viz::{self, Cell, Color, Frame},
+    Grid,
+};
+
+type Position = (usize, usize);
+
+struct Input {
+    start: Position,
+    end: Position,
+    grid: Grid<u8>,
+}
+
+impl solve::Input for Input {
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let grid = Grid::parse_with(reader, |c| match c {
+            'S' | 'E' | 'a'..='z' => Ok(c as u8),
+            _ => Err(anyhow!("expected an elevation")),
+        })?;
+        let find = |marker| grid.iter().find(|(_, c)| **c == marker).map(|(p, _)| p);
+        let start = find(b'S').context("missing start")?;
+        let end = find(b'E').context("missing end")?;
+
+        let grid = grid.map(|c| match c {
+            b'S' => 0,
+            b'E' => 25,
+            _ => c - b'a',
+        });
+
+        Ok(Self { start, end, grid })
+    }
+}
+
+/// Draws the heightmap as a search across it reaches `current`, with the
+/// squares it has expanded and the ones queued up next to them.
+fn frame(
+    input: &Input,
+    expanded: &HashSet<Position>,
+    current: Position,
+    can_step: impl Fn(Position, Position) -> bool,
+) -> Frame {
+    let grid = &input.grid;
+    let letter = |position: Position| (b'a' + grid[position]) as char;
+    let queued = expanded
+        .iter()
+        .flat_map(|&(x, y)| {
+            let can_step = &can_step;
+            grid.neighbors_4(x, y)
+                .filter(move |&next| can_step((x, y), next))
+        })
+        .filter(|next| !expanded.contains(next))
+        .collect::<HashSet<_>>();
+
+    let mut frame = Frame::new().grid(grid, |&height| {
+        Some(Cell::new((b'a' + height) as char, Color::Gray))
+    });
+    for &position in expanded {
+        frame = frame.cell(position, Cell::new(letter(position), Color::Blue));
+    }
+    for &position in &queued {
+        frame = frame.cell(position, Cell::new(letter(position), Color::Yellow));
+    }
+    frame
+        .cell(input.start, Cell::new('S', Color::Green))
+        .cell(input.end, Cell::new('E', Color::Green))
+        .cell(current, Cell::new(letter(current), Color::Red))
+        .caption(format!(
+            "{} squares expanded, {} queued",
+            expanded.len(),
+            queued.len()
+        ))
+}
+
+fn solve_part_one(input: &Input) -> Result<usize> {
+    let grid = &input.grid;
+    let can_climb = |from: Position, to: Position| grid[to] <= grid[from] + 1;
+    let mut expanded = HashSet::new();
+    search::bfs(
+        input.start,
+        |&(x, y)| {
+            grid.neighbors_4(x, y)
+                .filter(move |&next| can_climb((x, y), next))
+        },
+        |&position| {
+            if viz::enabled() {
+                expanded.insert(position);
+                viz::show(|| frame(input, &expanded, position, can_climb));
+            }
+            position == input.end
+        },
+    )
+    .path
+    .map(|path| path.cost)
+    .context("no path from the start to the end")
+}
+
+fn solve_part_two(input: &Input) -> Result<usize> {
+    // Search backwards from the end for the nearest lowest square
+    let grid = &input.grid;
+    let can_descend = |from: Position, to: Position| grid[to] + 1 >= grid[from];
+    let mut expanded = HashSet::new();
+    search::bfs(
+        input.end,
+        |&(x, y)| {
+            grid.neighbors_4(x, y)
+                .filter(move |&next| can_descend((x, y), next))
+        },
+        |&position| {
+            if viz::enabled() {
+                expanded.insert(position);
+                viz::show(|| frame(input, &expanded, position, can_descend));
+            }
+            grid[position] == 0
+        },
+    )
+    .path
+    .map(|path| path.cost)
+    .context("no path from the end to a lowest square")
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(12, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
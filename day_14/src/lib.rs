@@ -0,0 +1,158 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::Error;
+use solve::{
+    geom::{Rect, Vec2i},
+    viz::{self, Cell, Color, Frame},
+};
+
+const DOWN: Vec2i = Vec2i::new(0, 1);
+const DOWN_LEFT: Vec2i = Vec2i::new(-1, 1);
+const DOWN_RIGHT: Vec2i = Vec2i::new(1, 1);
+
+/// Whether sand falling from above could still land inside `bounds`.
+fn could_contain(bounds: &Rect<i32>, point: Vec2i) -> bool {
+    bounds.min.x <= point.x && bounds.max.x >= point.x && bounds.max.y >= point.y
+}
+
+struct Polyline {
+    points: Vec<Vec2i>,
+}
+
+impl Polyline {
+    fn bounding_box(&self) -> Option<Rect<i32>> {
+        Rect::bounding(self.points.iter().copied())
+    }
+
+    fn overlaps(&self, point: Vec2i) -> bool {
+        self.points
+            .iter()
+            .zip(self.points.iter().skip(1))
+            .any(|(a, b)| Rect::new(*a, *b).contains(point))
+    }
+}
+
+impl FromStr for Polyline {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            points: s
+                .split(" -> ")
+                .map(|p| p.parse())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+/// Draws the rock, the floor if there is one, and the sand that has come to
+/// rest so far.
+fn frame(lines: &[Polyline], sand: &HashSet<Vec2i>, floor: Option<i32>) -> Frame {
+    let rock = lines
+        .iter()
+        .flat_map(|line| line.points.windows(2))
+        .flat_map(|segment| {
+            let rect = Rect::new(segment[0], segment[1]);
+            (rect.min.x..=rect.max.x)
+                .flat_map(move |x| (rect.min.y..=rect.max.y).map(move |y| Vec2i::new(x, y)))
+        });
+    // Sand can't spread further than the floor is below the source
+    let floor = floor
+        .into_iter()
+        .flat_map(|floor| (500 - floor..=500 + floor).map(move |x| Vec2i::new(x, floor)));
+    Frame::new()
+        .points(rock.chain(floor), Cell::new('#', Color::Gray))
+        .cell(Vec2i::new(500, 0), Cell::new('+', Color::Cyan))
+        .points(sand, Cell::new('o', Color::Yellow))
+        .caption(format!("{} units of sand at rest", sand.len()))
+}
+
+fn try_move(
+    point: Vec2i,
+    sand: &HashSet<Vec2i>,
+    lines: &[Polyline],
+    floor: Option<i32>,
+) -> Option<Vec2i> {
+    if sand.contains(&point)
+        || lines.iter().any(|l| l.overlaps(point))
+        || floor.is_some_and(|floor| point.y >= floor)
+    {
+        None
+    } else {
+        Some(point)
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_one(input: &Vec<Polyline>) -> usize {
+    let bounds = input
+        .iter()
+        .map(|p| Polyline::bounding_box(p).unwrap())
+        .reduce(Rect::merge)
+        .unwrap();
+    let mut sand = HashSet::new();
+
+    loop {
+        let mut drop = Vec2i::new(500, 0);
+        while could_contain(&bounds, drop) {
+            if let Some(next) = try_move(drop + DOWN, &sand, input, None) {
+                drop = next;
+            } else if let Some(next) = try_move(drop + DOWN_LEFT, &sand, input, None) {
+                drop = next;
+            } else if let Some(next) = try_move(drop + DOWN_RIGHT, &sand, input, None) {
+                drop = next;
+            } else {
+                sand.insert(drop);
+                viz::show(|| frame(input, &sand, None));
+                break;
+            }
+        }
+        if !could_contain(&bounds, drop) {
+            break;
+        }
+    }
+
+    sand.len()
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_two(input: &Vec<Polyline>) -> usize {
+    let bounds = input
+        .iter()
+        .map(|p| Polyline::bounding_box(p).unwrap())
+        .reduce(Rect::merge)
+        .unwrap();
+    let floor = Some(bounds.max.y + 2);
+    let mut sand = HashSet::new();
+
+    while !sand.contains(&Vec2i::new(500, 0)) {
+        let mut drop = Vec2i::new(500, 0);
+        loop {
+            if let Some(next) = try_move(drop + DOWN, &sand, input, floor) {
+                drop = next;
+            } else if let Some(next) = try_move(drop + DOWN_LEFT, &sand, input, floor) {
+                drop = next;
+            } else if let Some(next) = try_move(drop + DOWN_RIGHT, &sand, input, floor) {
+                drop = next;
+            } else {
+                sand.insert(drop);
+                viz::show(|| frame(input, &sand, floor));
+                break;
+            }
+        }
+    }
+
+    sand.len()
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(14, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
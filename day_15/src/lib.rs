@@ -0,0 +1,191 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+use solve::{
+    cancel,
+    geom::Vec2i,
+    par,
+    parser::{self, preceded, Grammar, Parser},
+    Params,
+};
+
+struct Sensor {
+    position: Vec2i,
+    nearest_beacon: Vec2i,
+}
+
+impl Sensor {
+    fn covered_range_at(&self, y: i32) -> Option<(i32, i32)> {
+        let covered_radius = self.position.manhattan(self.nearest_beacon);
+        let distance = (y - self.position.y).abs();
+        let range_radius = covered_radius - distance;
+        if range_radius < 0 {
+            None
+        } else {
+            Some((
+                self.position.x - range_radius,
+                self.position.x + range_radius,
+            ))
+        }
+    }
+}
+
+impl Grammar for Sensor {
+    fn grammar<'a>() -> impl Parser<'a, Self> {
+        (
+            preceded("Sensor at ", Vec2i::grammar()),
+            preceded(": closest beacon is at ", Vec2i::grammar()),
+        )
+            .map(|(position, nearest_beacon)| Self {
+                position,
+                nearest_beacon,
+            })
+    }
+}
+
+impl FromStr for Sensor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parser::from_str(s)?)
+    }
+}
+
+/// The ranges of x the sensors cover in `row`, merged so that none overlap
+/// or touch, in order.
+fn covered_ranges(input: &[Sensor], row: i32) -> Vec<(i32, i32)> {
+    let mut ranges = input
+        .iter()
+        .filter_map(|s| s.covered_range_at(row))
+        .collect::<Vec<_>>();
+    ranges.sort();
+
+    let mut merged = Vec::<(i32, i32)>::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.0 <= last.1 + 1 => last.1 = i32::max(last.1, range.1),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// How many positions in `row` can't contain a beacon.
+fn beaconless_positions(input: &[Sensor], row: i32) -> i32 {
+    let beacons = input
+        .iter()
+        .filter_map(|s| {
+            if s.nearest_beacon.y == row {
+                Some(s.nearest_beacon.x)
+            } else {
+                None
+            }
+        })
+        .collect::<HashSet<_>>();
+
+    covered_ranges(input, row)
+        .into_iter()
+        .map(|(start, end)| {
+            end - start + 1 - beacons.iter().filter(|&&x| x >= start && x <= end).count() as i32
+        })
+        .sum()
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_one(input: &Vec<Sensor>, params: &Params) -> i32 {
+    beaconless_positions(input, params.get("row"))
+}
+
+/// Describes what the sensors cover in the row given in `args`.
+#[allow(clippy::ptr_arg)]
+fn show_row(input: &Vec<Sensor>, args: &[&str]) -> Result<String> {
+    let &[row] = args else {
+        bail!("expected a row number");
+    };
+    let row = row
+        .parse()
+        .with_context(|| format!("`{row}` isn't a row number"))?;
+    let mut lines = covered_ranges(input, row)
+        .into_iter()
+        .map(|(start, end)| format!("covered from x={start} to x={end}"))
+        .collect::<Vec<_>>();
+    lines.push(format!(
+        "{} positions can't contain a beacon",
+        beaconless_positions(input, row)
+    ));
+    Ok(lines.join("\n"))
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_two(input: &Vec<Sensor>, params: &Params) -> Result<u64> {
+    let search_space = params.get("search_space");
+
+    // Scan blocks of rows in parallel, stopping at the first gap
+    const BLOCK: i32 = 10_000;
+    let gap = par::find_map_first((0..search_space).step_by(BLOCK as usize), |first| {
+        // Nobody will see the answer once cancelled, so stop scanning
+        let rows = first..i32::min(first + BLOCK, search_space);
+        rows.take_while(|_| !cancel::is_cancelled())
+            .find_map(|row| {
+                let mut covered_ranges = input
+                    .iter()
+                    .filter_map(|s| s.covered_range_at(row))
+                    .filter(|r| r.1 >= 0 && r.0 <= search_space)
+                    .collect::<Vec<_>>();
+                covered_ranges.sort();
+
+                let mut last_range = None;
+                let mut active_range = Option::<(i32, i32)>::None;
+                for range in covered_ranges {
+                    if let Some(active) = active_range {
+                        if range.0 <= active.1 + 1 {
+                            active_range = Some((active.0, i32::max(active.1, range.1)));
+                        } else {
+                            last_range = Some(active);
+                            active_range = Some(range);
+                        }
+                    } else {
+                        active_range = Some(range);
+                    }
+                }
+                last_range.map(|last_range| (last_range.1 + 1) as u64 * 4_000_000u64 + row as u64)
+            })
+    })?;
+
+    gap.context("every position in the search space is covered")
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::with_params(15, solve_part_one, solve_part_two)
+        .param("row", 2_000_000)
+        .param("search_space", 4_000_000)
+        .command(
+            "row Y",
+            "show the ranges the sensors cover in row Y",
+            show_row,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Sensor, Vec2i};
+
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+
+    #[test]
+    fn test_covered_range() {
+        let sensor = Sensor {
+            position: Vec2i { x: 8, y: 7 },
+            nearest_beacon: Vec2i { x: 2, y: 10 },
+        };
+        assert_eq!(sensor.covered_range_at(-3), None);
+        assert_eq!(sensor.covered_range_at(-2), Some((8, 8)));
+        assert_eq!(sensor.covered_range_at(7), Some((-1, 17)));
+        assert_eq!(sensor.covered_range_at(10), Some((2, 14)));
+        assert_eq!(sensor.covered_range_at(14), Some((6, 10)));
+        assert_eq!(sensor.covered_range_at(22), None);
+    }
+}
@@ -0,0 +1,254 @@
+use std::{collections::HashMap, io::BufRead, iter};
+
+use anyhow::{Context, Result};
+use bitvec::prelude::*;
+use solve::{search, Input, Params};
+
+#[derive(Clone, Debug, Default)]
+struct Valve {
+    flow_rate: usize,
+    tunnels: Vec<(usize, usize)>,
+}
+
+#[derive(Clone, Debug)]
+struct Graph {
+    valves: Vec<Valve>,
+    labels: Vec<String>,
+    start: usize,
+}
+
+impl Graph {
+    fn densify(&mut self) {
+        for i in 0..self.valves.len() {
+            if i != self.start && self.valves[i].flow_rate == 0 {
+                // Remove this valve and directly connect its neighbors
+                let tunnels = std::mem::take(&mut self.valves[i].tunnels);
+                for &(a, a_dist) in tunnels.iter() {
+                    // Remove this valve from its neighbor
+                    self.valves[a].tunnels.retain(|(to, _)| *to != i);
+                    // Update the distances it has to this valve's neighbors
+                    for &(b, b_dist) in tunnels.iter().filter(|(to, _)| *to != a) {
+                        if let Some(dist) = self.valves[a]
+                            .tunnels
+                            .iter_mut()
+                            .find_map(|(to, dist)| (*to == b).then_some(dist))
+                        {
+                            *dist = usize::min(*dist, a_dist + b_dist);
+                        } else {
+                            self.valves[a].tunnels.push((b, a_dist + b_dist));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lists each valve that still has tunnels, with its flow rate and how
+    /// long each tunnel takes to walk.
+    fn describe(&self) -> String {
+        let mut lines = Vec::new();
+        for (valve, label) in self.valves.iter().zip(&self.labels) {
+            if valve.tunnels.is_empty() {
+                continue;
+            }
+            let tunnels = valve
+                .tunnels
+                .iter()
+                .map(|&(to, distance)| format!("{} ({distance})", self.labels[to]))
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "{label} flow rate {}: {}",
+                valve.flow_rate,
+                tunnels.join(", ")
+            ));
+        }
+        let flowing = self.valves.iter().filter(|v| v.flow_rate > 0).count();
+        lines.push(format!(
+            "{} valves, {flowing} with a nonzero flow rate",
+            lines.len()
+        ));
+        lines.join("\n")
+    }
+}
+
+impl Input for Graph {
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let mut valves = Vec::new();
+        let mut labels = Vec::new();
+        let mut start = None;
+        let mut label_to_index = HashMap::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let (label, line) = line
+                .strip_prefix("Valve ")
+                .context("expected valve prefix")?
+                .split_at(2);
+            let mut pieces = line
+                .strip_prefix(" has flow rate=")
+                .context("expected flow rate prefix")?
+                .split("; ");
+            let flow_rate = pieces.next().context("expected flow rate")?;
+            let flow_rate = flow_rate.parse()?;
+
+            let i = valves.len();
+            valves.insert(
+                i,
+                Valve {
+                    flow_rate,
+                    tunnels: Vec::new(),
+                },
+            );
+            labels.push(label.to_string());
+            label_to_index.insert(label.to_string(), i);
+            if label == "AA" {
+                start = Some(i);
+            }
+
+            let mut tunnels = pieces.next().context("expected tunnels")?.split(", ");
+            let first = tunnels.next().context("expected one tunnel")?;
+            let (_, first) = first.split_at(first.len() - 2);
+            for tunnel in iter::once(first).chain(tunnels) {
+                if let Some(&to) = label_to_index.get(tunnel) {
+                    valves[i].tunnels.push((to, 1));
+                    valves[to].tunnels.push((i, 1));
+                }
+            }
+        }
+
+        Ok(Self {
+            valves,
+            labels,
+            start: start.unwrap(),
+        })
+    }
+}
+
+struct State {
+    valve: usize,
+    valve_states: BitArray<u64, Lsb0>,
+    time_left: usize,
+    pressure_released: usize,
+}
+
+impl Graph {
+    fn successors(&self, current: &State) -> Vec<State> {
+        let mut next = Vec::new();
+
+        // Turn on the valve in this room
+        if current.time_left > 0
+            && !current.valve_states[current.valve]
+            && self.valves[current.valve].flow_rate != 0
+        {
+            let mut valve_states = current.valve_states;
+            valve_states.set(current.valve, true);
+            next.push(State {
+                valve: current.valve,
+                valve_states,
+                time_left: current.time_left - 1,
+                pressure_released: current.pressure_released
+                    + self.valves[current.valve].flow_rate * (current.time_left - 1),
+            });
+        }
+
+        for &(to, dist) in self.valves[current.valve].tunnels.iter() {
+            // Move to another valve
+            if current.time_left > dist {
+                next.push(State {
+                    valve: to,
+                    valve_states: current.valve_states,
+                    time_left: current.time_left - dist,
+                    pressure_released: current.pressure_released,
+                });
+            }
+        }
+
+        next
+    }
+
+    /// Visits every state worth considering within `minutes`. Each state could
+    /// also wait where it is for the remaining time.
+    fn explore(&self, minutes: usize) -> impl Iterator<Item = State> + '_ {
+        let start = State {
+            valve: self.start,
+            valve_states: BitArray::ZERO,
+            time_left: minutes,
+            pressure_released: 0,
+        };
+
+        // Being at the same valve with the same valves open is no better than
+        // an earlier visit that had at least as much time and pressure
+        let dominance = search::Dominance::new(
+            |s: &State| {
+                (
+                    (s.valve, s.valve_states),
+                    (s.time_left, s.pressure_released),
+                )
+            },
+            |a: &(usize, usize), b: &(usize, usize)| a.0 >= b.0 && a.1 >= b.1,
+        );
+
+        search::traverse(start, |s| self.successors(s), dominance)
+    }
+}
+
+fn solve_part_one(input: &Graph, params: &Params) -> usize {
+    let mut graph = input.clone();
+    graph.densify();
+
+    graph
+        .explore(params.get("minutes"))
+        .map(|s| s.pressure_released)
+        .max()
+        .unwrap()
+}
+
+fn solve_part_two(input: &Graph, params: &Params) -> usize {
+    let mut graph = input.clone();
+    graph.densify();
+
+    let mut max_pressure_released = HashMap::new();
+    for state in graph.explore(params.get("minutes_with_elephant")) {
+        let best = max_pressure_released.entry(state.valve_states).or_insert(0);
+        *best = usize::max(*best, state.pressure_released);
+    }
+
+    let mut max_total = 0;
+    for (you_valves, you_pressure) in max_pressure_released.iter() {
+        for (elephant_valves, elephant_pressure) in max_pressure_released.iter() {
+            if *you_valves & *elephant_valves == BitArray::<u64, Lsb0>::ZERO {
+                max_total = usize::max(max_total, you_pressure + elephant_pressure);
+            }
+        }
+    }
+
+    max_total
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::with_params(16, solve_part_one, solve_part_two)
+        .param("minutes", 30usize)
+        .param("minutes_with_elephant", 26usize)
+        .command(
+            "valves",
+            "list the valves and the tunnels between them",
+            |graph: &Graph, _: &[&str]| graph.describe(),
+        )
+        .command(
+            "densify",
+            "list the valves left once those without flow are walked through",
+            |graph: &Graph, _: &[&str]| {
+                let mut graph = graph.clone();
+                graph.densify();
+                graph.describe()
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
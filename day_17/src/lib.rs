@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use solve::{
+    viz::{self, Cell, Color, Frame},
+    BorrowedInput, Grid, InputRef, Params,
+};
+
+/// The jets of hot gas, borrowed straight from the input since each one is
+/// already a single `<` or `>` byte.
+struct Pattern<'a> {
+    jets: &'a [u8],
+}
+
+impl Pattern<'_> {
+    /// How far the jet at `index` pushes a rock sideways.
+    fn push(&self, index: usize) -> i64 {
+        match self.jets[index] {
+            b'<' => -1,
+            _ => 1,
+        }
+    }
+}
+
+impl<'a> InputRef<'a> for Pattern<'a> {
+    fn parse_ref(bytes: &'a [u8]) -> Result<Self> {
+        let jets = bytes.split(|b| *b == b'\n').next().unwrap_or_default();
+        // Check everything up front so pushing can't fail
+        if let Some(b) = jets.iter().find(|b| !matches!(b, b'<' | b'>')) {
+            bail!("expected > or <, found {b}");
+        }
+        if jets.is_empty() {
+            bail!("expected at least one jet");
+        }
+        Ok(Self { jets })
+    }
+}
+
+impl BorrowedInput for Pattern<'static> {
+    type Input<'a> = Pattern<'a>;
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+enum RockKind {
+    Dash,
+    Plus,
+    L,
+    I,
+    Square,
+}
+
+impl RockKind {
+    fn width(self) -> i64 {
+        match self {
+            Self::Dash => 4,
+            Self::Plus => 3,
+            Self::L => 3,
+            Self::I => 1,
+            Self::Square => 2,
+        }
+    }
+
+    fn height(self) -> i64 {
+        match self {
+            Self::Dash => 1,
+            Self::Plus => 3,
+            Self::L => 3,
+            Self::I => 4,
+            Self::Square => 2,
+        }
+    }
+
+    fn is_set(self, x: i64, y: i64) -> bool {
+        match self {
+            Self::Dash | Self::I | Self::Square => true,
+            Self::Plus => x == 1 || y == 1,
+            Self::L => x == 2 || y == 2,
+        }
+    }
+}
+
+struct Rock {
+    x: i64,
+    y: i64,
+    kind: RockKind,
+}
+
+impl Rock {
+    fn overlap(&self, chamber: &Chamber) -> bool {
+        for x in 0..self.kind.width() {
+            for y in 0..self.kind.height() {
+                if self.kind.is_set(x, y) && chamber.get(self.x + x, self.y - y) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// The positions the rock covers.
+    fn cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (0..self.kind.width()).flat_map(move |x| {
+            (0..self.kind.height())
+                .filter(move |&y| self.kind.is_set(x, y))
+                .map(move |y| (self.x + x, self.y - y))
+        })
+    }
+
+    fn blit(&self, chamber: &mut Chamber) {
+        for x in 0..self.kind.width() {
+            for y in 0..self.kind.height() {
+                if self.kind.is_set(x, y) {
+                    chamber.set(self.x + x, self.y - y);
+                }
+            }
+        }
+    }
+}
+
+/// The rows of the chamber near the top of the tower. Rows further down can
+/// no longer be reached, so the grid is reused from the bottom as the tower
+/// grows.
+struct Chamber {
+    cells: Grid<bool>,
+    floor: u64,
+}
+
+impl Chamber {
+    const WIDTH: usize = 7;
+    const ROWS: u64 = 64;
+
+    fn new() -> Self {
+        Chamber {
+            cells: Grid::new(Self::WIDTH, Self::ROWS as usize, false),
+            floor: 0,
+        }
+    }
+
+    fn cell(x: i64, y: i64) -> (usize, usize) {
+        (x as usize, (y as u64 % Self::ROWS) as usize)
+    }
+
+    fn get(&self, x: i64, y: i64) -> bool {
+        if !(0..Self::WIDTH as i64).contains(&x) || y < self.floor as i64 {
+            true
+        } else {
+            self.cells[Self::cell(x, y)]
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64) {
+        self.cells[Self::cell(x, y)] = true;
+    }
+
+    /// Each row as a bitmask, for remembering the chamber's shape cheaply.
+    fn packed(&self) -> [u8; Self::ROWS as usize] {
+        let mut rows = self.cells.rows();
+        std::array::from_fn(|_| {
+            let row = rows.next().unwrap();
+            (0..Self::WIDTH).fold(0, |bits, x| bits | (row[x] as u8) << x)
+        })
+    }
+
+    fn reserve_to(&mut self, height: i64) {
+        let height = height as u64;
+        if self.floor + Self::ROWS < height {
+            for y in self.floor..height - Self::ROWS {
+                for x in 0..Self::WIDTH as i64 {
+                    self.cells[Self::cell(x, y as i64)] = false;
+                }
+            }
+            self.floor = height - Self::ROWS;
+        }
+    }
+}
+
+/// Draws the top of the tower up to `top`, with `rock` in a different colour
+/// since it only just came to rest.
+fn frame(chamber: &Chamber, rock: &Rock, top: i64, dropped: u64) -> Frame {
+    // The tower grows upwards but frames are drawn downwards
+    let bottom = i64::max(chamber.floor as i64, top - 40);
+    let mut frame = Frame::new();
+    for y in bottom..=top {
+        frame = frame
+            .cell((-1, -y), Cell::plain('|'))
+            .cell((7, -y), Cell::plain('|'));
+        for x in 0..7 {
+            if chamber.get(x, y) {
+                frame = frame.cell((x, -y), Cell::new('#', Color::Gray));
+            }
+        }
+    }
+    if bottom == 0 {
+        frame = frame
+            .points((-1..=7).map(|x| (x, 1)), Cell::plain('-'))
+            .cell((-1, 1), Cell::plain('+'))
+            .cell((7, 1), Cell::plain('+'));
+    }
+    frame
+        .points(
+            rock.cells().map(|(x, y)| (x, -y)),
+            Cell::new('@', Color::Yellow),
+        )
+        .caption(format!("{dropped} rocks dropped, {} units tall", top + 1))
+}
+
+#[derive(Eq, Hash, PartialEq)]
+struct State {
+    rows: [u8; Chamber::ROWS as usize],
+    pattern_index: usize,
+    kind: RockKind,
+}
+
+fn simulate(pattern: &Pattern, count: u64) -> i64 {
+    let mut chamber = Chamber::new();
+    let mut kind = RockKind::Dash;
+    let mut pattern_index = 0;
+
+    let mut seen = HashMap::new();
+
+    let mut highest_rock_pos = -1;
+    let mut i = 0;
+    while i < count {
+        let state = State {
+            rows: chamber.packed(),
+            pattern_index,
+            kind,
+        };
+        if let Some((prev_highest_rock_pos, prev_i)) = seen.insert(state, (highest_rock_pos, i)) {
+            let rocks_dropped = i - prev_i;
+            let height_change = highest_rock_pos - prev_highest_rock_pos;
+
+            let advance = (count - i) / rocks_dropped;
+
+            i += advance * rocks_dropped;
+            highest_rock_pos += advance as i64 * height_change;
+            chamber.floor += advance * height_change as u64;
+        }
+
+        let mut rock = Rock {
+            x: 2,
+            y: highest_rock_pos + kind.height() + 3,
+            kind,
+        };
+
+        kind = match kind {
+            RockKind::Dash => RockKind::Plus,
+            RockKind::Plus => RockKind::L,
+            RockKind::L => RockKind::I,
+            RockKind::I => RockKind::Square,
+            RockKind::Square => RockKind::Dash,
+        };
+
+        loop {
+            let push = pattern.push(pattern_index);
+            pattern_index = (pattern_index + 1) % pattern.jets.len();
+
+            rock.x += push;
+            if rock.overlap(&chamber) {
+                rock.x -= push;
+            }
+            rock.y -= 1;
+            if rock.overlap(&chamber) {
+                rock.y += 1;
+                break;
+            }
+        }
+
+        rock.blit(&mut chamber);
+        highest_rock_pos = i64::max(rock.y, highest_rock_pos);
+        viz::show(|| frame(&chamber, &rock, highest_rock_pos, i + 1));
+        chamber.reserve_to(highest_rock_pos + kind.height() + 4);
+        i += 1;
+    }
+
+    highest_rock_pos + 1
+}
+
+fn solve_part_one(pattern: &Pattern, params: &Params) -> i64 {
+    simulate(pattern, params.get("rocks"))
+}
+
+fn solve_part_two(pattern: &Pattern, params: &Params) -> i64 {
+    simulate(pattern, params.get("many_rocks"))
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::borrowing::<Pattern<'static>, _, _>(17, solve_part_one, solve_part_two)
+        .param("rocks", 2022u64)
+        .param("many_rocks", 1_000_000_000_000u64)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+
+use solve::{
+    geom::{Cuboid, Vec3i},
+    viz::{self, Color, Frame},
+};
+
+fn grid_index(position: Vec3i, size: Vec3i) -> usize {
+    (position.x + size.x * (position.y + size.y * position.z)) as usize
+}
+
+const NEIGHBORS: [Vec3i; 6] = [
+    Vec3i::new(-1, 0, 0),
+    Vec3i::new(1, 0, 0),
+    Vec3i::new(0, -1, 0),
+    Vec3i::new(0, 1, 0),
+    Vec3i::new(0, 0, -1),
+    Vec3i::new(0, 0, 1),
+];
+
+fn solve_part_one(input: &Vec<Vec3i>) -> usize {
+    let mut total = 0;
+
+    let mut positions = HashSet::new();
+    for position in input {
+        positions.insert(position);
+        total += 6;
+        for neighbor in NEIGHBORS {
+            if positions.contains(&(*position + neighbor)) {
+                total -= 2;
+            }
+        }
+    }
+
+    total
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    None,
+    Lava,
+    Steam,
+}
+
+impl Cell {
+    fn draw(self) -> Option<viz::Cell> {
+        match self {
+            Cell::None => None,
+            Cell::Lava => Some(viz::Cell::new('#', Color::Rgb(207, 70, 25))),
+            Cell::Steam => Some(viz::Cell::new('~', Color::Rgb(170, 200, 230))),
+        }
+    }
+}
+
+/// Draws each z slice of the grid side by side, in rows of slices.
+fn frame(grid: &[Cell], size: Vec3i, sweep: usize) -> Frame {
+    let columns = (size.z as f64).sqrt().ceil() as i32;
+    let mut frame = Frame::new();
+    for z in 0..size.z {
+        let left = z % columns * (size.x + 1);
+        let top = z / columns * (size.y + 1);
+        for y in 0..size.y {
+            for x in 0..size.x {
+                if let Some(cell) = grid[grid_index(Vec3i::new(x, y, z), size)].draw() {
+                    frame = frame.cell((left + x, top + y), cell);
+                }
+            }
+        }
+    }
+    frame.caption(format!("sweep {sweep}"))
+}
+
+fn solve_part_two(input: &Vec<Vec3i>) -> i32 {
+    let bounds = Cuboid::bounding(input.iter().copied()).unwrap();
+    let lower = bounds.min;
+    let size = bounds.size();
+    let local_bounds = Cuboid::new(Vec3i::ZERO, size - Vec3i::new(1, 1, 1));
+
+    let mut grid = vec![Cell::None; size.product() as usize];
+
+    for i in input {
+        let index = grid_index(*i - lower, size);
+        grid[index] = Cell::Lava;
+    }
+
+    let mut done = false;
+    let mut sweep = 0;
+    while !done {
+        done = true;
+        sweep += 1;
+
+        for x in 0..size.x {
+            for y in 0..size.y {
+                for z in 0..size.z {
+                    let local_pos = Vec3i::new(x, y, z);
+                    let index = grid_index(local_pos, size);
+                    if grid[index] == Cell::None {
+                        for neighbor in NEIGHBORS {
+                            let neighbor_pos = local_pos + neighbor;
+                            if !local_bounds.contains(neighbor_pos)
+                                || grid[grid_index(neighbor_pos, size)] == Cell::Steam
+                            {
+                                grid[index] = Cell::Steam;
+                                done = false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        viz::show(|| frame(&grid, size, sweep));
+    }
+
+    let mut total = 0;
+    for pos in input {
+        let local_pos = *pos - lower;
+        for neighbor in NEIGHBORS {
+            let neighbor_pos = local_pos + neighbor;
+            if !local_bounds.contains(neighbor_pos)
+                || grid[grid_index(neighbor_pos, size)] == Cell::Steam
+            {
+                total += 1;
+            }
+        }
+    }
+
+    total
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(18, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
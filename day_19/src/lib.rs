@@ -0,0 +1,233 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::{Context, Error};
+use solve::{cancel::Cancelled, par, search};
+
+#[derive(Debug)]
+struct Blueprint {
+    ore_robot_ore: u8,
+    clay_robot_ore: u8,
+    obsidian_robot_ore: u8,
+    obsidian_robot_clay: u8,
+    geode_robot_ore: u8,
+    geode_robot_obsidian: u8,
+}
+
+impl FromStr for Blueprint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .strip_prefix("Blueprint ")
+            .context("expected blueprint prefix")?;
+        let mut pieces = s.split(": ");
+        let _ = pieces
+            .next()
+            .context("expected blueprint number")?
+            .parse::<usize>()?;
+        let s = pieces.next().context("expected blueprint description")?;
+        let mut pieces = s.split(". ");
+
+        let ore_robot_ore = pieces
+            .next()
+            .context("expected ore robot blueprint")?
+            .strip_prefix("Each ore robot costs ")
+            .context("expected ore robot prefix")?
+            .strip_suffix(" ore")
+            .context("expected ore robot suffix")?
+            .parse()?;
+        let clay_robot_ore = pieces
+            .next()
+            .context("expected clay robot blueprint")?
+            .strip_prefix("Each clay robot costs ")
+            .context("expected clay robot prefix")?
+            .strip_suffix(" ore")
+            .context("expected clay robot suffix")?
+            .parse()?;
+
+        let mut obsidian_robot_pieces = pieces
+            .next()
+            .context("expected obsidian robot blueprint")?
+            .strip_prefix("Each obsidian robot costs ")
+            .context("expected obsidian robot prefix")?
+            .split(" ore and ");
+        let obsidian_robot_ore = obsidian_robot_pieces
+            .next()
+            .context("expected obsidian robot ore cost")?
+            .parse()?;
+        let obsidian_robot_clay = obsidian_robot_pieces
+            .next()
+            .context("expected obsidian robot clay cost")?
+            .strip_suffix(" clay")
+            .context("expected obsidian robot suffix")?
+            .parse()?;
+
+        let mut geode_robot_pieces = pieces
+            .next()
+            .context("expected geode robot blueprint")?
+            .strip_prefix("Each geode robot costs ")
+            .context("expected geode robot prefix")?
+            .split(" ore and ");
+        let geode_robot_ore = geode_robot_pieces
+            .next()
+            .context("expected geode robot ore cost")?
+            .parse()?;
+        let geode_robot_obsidian = geode_robot_pieces
+            .next()
+            .context("expected geode robot obsidian cost")?
+            .strip_suffix(" obsidian.")
+            .context("expected geode robot suffix")?
+            .parse()?;
+
+        Ok(Self {
+            ore_robot_ore,
+            clay_robot_ore,
+            obsidian_robot_ore,
+            obsidian_robot_clay,
+            geode_robot_ore,
+            geode_robot_obsidian,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct State {
+    time_left: u8,
+    geodes: u8,
+    ore_robots: u8,
+    ore: u8,
+    clay_robots: u8,
+    clay: u8,
+    obsidian_robots: u8,
+    obsidian: u8,
+}
+
+impl Blueprint {
+    fn successors(&self, state: &State) -> Vec<State> {
+        // No time left to spend doing anything
+        if state.time_left == 0 {
+            return Vec::new();
+        }
+
+        // Spend a minute
+        let time_left = state.time_left - 1;
+
+        // Passive effects
+        let ore = state.ore + state.ore_robots;
+        let clay = state.clay + state.clay_robots;
+        let obsidian = state.obsidian + state.obsidian_robots;
+
+        // Do nothing
+        let mut next = vec![State {
+            time_left,
+            ore,
+            clay,
+            obsidian,
+            ..*state
+        }];
+
+        // Build an ore robot
+        if state.ore >= self.ore_robot_ore {
+            next.push(State {
+                time_left,
+                ore: ore - self.ore_robot_ore,
+                ore_robots: state.ore_robots + 1,
+                clay,
+                obsidian,
+                ..*state
+            });
+        }
+
+        // Build a clay robot
+        if state.ore >= self.clay_robot_ore {
+            next.push(State {
+                time_left,
+                ore: ore - self.clay_robot_ore,
+                clay,
+                clay_robots: state.clay_robots + 1,
+                obsidian,
+                ..*state
+            });
+        }
+
+        // Build an obsidian robot
+        if state.ore >= self.obsidian_robot_ore && state.clay >= self.obsidian_robot_clay {
+            next.push(State {
+                time_left,
+                ore: ore - self.obsidian_robot_ore,
+                clay: clay - self.obsidian_robot_clay,
+                obsidian,
+                obsidian_robots: state.obsidian_robots + 1,
+                ..*state
+            });
+        }
+
+        // Build a geode robot (it produces `time_left` geodes)
+        if state.ore >= self.geode_robot_ore && state.obsidian >= self.geode_robot_obsidian {
+            next.push(State {
+                time_left,
+                geodes: state.geodes + time_left,
+                ore: ore - self.geode_robot_ore,
+                clay,
+                obsidian: obsidian - self.geode_robot_obsidian,
+                ..*state
+            });
+        }
+
+        next
+    }
+
+    fn quality(&self, time: usize) -> usize {
+        let start = State {
+            time_left: time as u8,
+            ore_robots: 1,
+            ..State::default()
+        };
+
+        // Max possible geodes is the current number of geodes plus the geodes
+        // from building a robot every turn until time is up.
+        let max_possible_geodes = |state: &State| {
+            let time_left = state.time_left as usize;
+            state.geodes as usize + time_left * time_left.saturating_sub(1) / 2
+        };
+
+        search::branch_and_bound(
+            start,
+            |state| self.successors(state),
+            |state| state.geodes as usize,
+            max_possible_geodes,
+            HashSet::new(),
+        )
+        .best
+        .map_or(0, |(_, geodes)| geodes)
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_one(input: &Vec<Blueprint>) -> Result<usize, Cancelled> {
+    let qualities = par::map(input.iter(), |b| b.quality(24))?;
+    Ok(qualities
+        .into_iter()
+        .enumerate()
+        .map(|(i, q)| (i + 1) * q)
+        .sum())
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_two(input: &Vec<Blueprint>) -> Result<usize, Cancelled> {
+    let qualities = par::map(input.iter().take(3), |b| b.quality(32))?;
+    Ok(qualities.into_iter().product())
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(19, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        #[ignore = "takes minutes without optimizations"]
+        answers,
+    }
+}
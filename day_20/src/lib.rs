@@ -0,0 +1,68 @@
+fn mix(input: &[i64], times: usize) -> Vec<i64> {
+    let len = input.len() as i64;
+
+    let mut derange = (0..input.len() as i64).collect::<Vec<i64>>();
+    for _ in 0..times {
+        for (i, offset) in input.iter().enumerate() {
+            if *offset == 0 {
+                continue;
+            }
+
+            let old_pos = derange[i];
+            let new_pos = (old_pos + offset % (len - 1) + len - 1) % (len - 1);
+            if old_pos < new_pos {
+                for pos in derange.iter_mut() {
+                    if *pos > old_pos && *pos <= new_pos {
+                        *pos = (*pos + len - 1) % len;
+                    }
+                }
+            } else {
+                for pos in derange.iter_mut() {
+                    if *pos >= new_pos && *pos < old_pos {
+                        *pos = (*pos + len + 1) % len;
+                    }
+                }
+            }
+
+            derange[i] = new_pos;
+        }
+    }
+
+    let mut output = vec![0; input.len()];
+    for (i, value) in derange.iter().zip(input.iter()) {
+        output[*i as usize] = *value;
+    }
+
+    output
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_one(input: &Vec<i64>) -> i64 {
+    let deranged = mix(input, 1);
+    let zero_pos = deranged.iter().position(|x| *x == 0).unwrap();
+    deranged[(zero_pos + 1000) % input.len()]
+        + deranged[(zero_pos + 2000) % input.len()]
+        + deranged[(zero_pos + 3000) % input.len()]
+}
+
+#[allow(clippy::ptr_arg)]
+fn solve_part_two(input: &Vec<i64>) -> i64 {
+    let real_input = input.iter().map(|x| x * 811589153).collect::<Vec<_>>();
+    let deranged = mix(&real_input, 10);
+    let zero_pos = deranged.iter().position(|x| *x == 0).unwrap();
+    deranged[(zero_pos + 1000) % input.len()]
+        + deranged[(zero_pos + 2000) % input.len()]
+        + deranged[(zero_pos + 3000) % input.len()]
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(20, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+use solve::{
+    cube::CubeNet,
+    geom::Direction,
+    parser::{self, integer, many1, Parser},
+    Grid, ParseError,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Clone, Copy)]
+enum Step {
+    Forward(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+struct Input {
+    board: Grid<Tile>,
+    path: Vec<Step>,
+}
+
+impl solve::Input for Input {
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let mut lines = reader.lines().enumerate();
+
+        let mut rows = Vec::new();
+        for (line_number, line) in lines.by_ref() {
+            let line = line?;
+            if line.is_empty() {
+                break;
+            }
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(column, c)| match c {
+                    ' ' => Ok(Tile::Void),
+                    '.' => Ok(Tile::Open),
+                    '#' => Ok(Tile::Wall),
+                    _ => Err(ParseError::new(
+                        "expected ' ', '.' or '#'",
+                        line_number + 1,
+                        column + 1,
+                        1,
+                        &line,
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+
+        // Rows stop at their last tile, so pad them out to a rectangle
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let board = Grid::from_fn(width, height, |x, y| {
+            rows[y].get(x).copied().unwrap_or(Tile::Void)
+        });
+
+        let (line_number, line) = lines.next().context("expected a path")?;
+        let line = line?;
+        let step = integer()
+            .map(Step::Forward)
+            .or("L".value(Step::TurnLeft))
+            .or("R".value(Step::TurnRight));
+        let path = parser::parse(many1(step), &line).map_err(|e| e.with_line(line_number + 1))?;
+
+        Ok(Self { board, path })
+    }
+}
+
+/// The score for facing in a direction, counting clockwise from right.
+fn facing(direction: Direction) -> usize {
+    (direction as usize + 3) % 4
+}
+
+fn password((x, y): (usize, usize), direction: Direction) -> usize {
+    1000 * (y + 1) + 4 * (x + 1) + facing(direction)
+}
+
+/// Follows `path` from the top-left open tile. `wrap` is called to find where
+/// stepping off the edge of the map from a position in a direction leads to,
+/// and the direction faced after wrapping.
+fn walk(
+    input: &Input,
+    mut wrap: impl FnMut((usize, usize), Direction) -> ((usize, usize), Direction),
+) -> Result<usize> {
+    let board = &input.board;
+    let start_x = board
+        .rows()
+        .next()
+        .and_then(|row| row.iter().position(|t| *t == Tile::Open))
+        .context("the top row has no open tile to start on")?;
+    let mut position = (start_x, 0);
+    let mut direction = Direction::Right;
+
+    for step in input.path.iter() {
+        match step {
+            Step::TurnLeft => direction = direction.turn_left(),
+            Step::TurnRight => direction = direction.turn_right(),
+            Step::Forward(distance) => {
+                for _ in 0..*distance {
+                    let next = step_on(board, position, direction)
+                        .map(|next| (next, direction))
+                        .unwrap_or_else(|| wrap(position, direction));
+                    if board[next.0] == Tile::Wall {
+                        break;
+                    }
+                    (position, direction) = next;
+                }
+            }
+        }
+    }
+
+    Ok(password(position, direction))
+}
+
+/// The tile next to `position` in `direction`, unless that's off the map.
+fn step_on(
+    board: &Grid<Tile>,
+    (x, y): (usize, usize),
+    direction: Direction,
+) -> Option<(usize, usize)> {
+    let delta = direction.to_vec();
+    board
+        .offset(x, y, delta.x, delta.y)
+        .filter(|&next| board[next] != Tile::Void)
+}
+
+fn solve_part_one(input: &Input) -> Result<usize> {
+    walk(input, |position, direction| {
+        // Walk back to the far edge of the map
+        let mut wrapped = position;
+        while let Some(back) = step_on(&input.board, wrapped, direction.opposite()) {
+            wrapped = back;
+        }
+        (wrapped, direction)
+    })
+}
+
+fn solve_part_two(input: &Input) -> Result<usize> {
+    let cube = CubeNet::fold(&input.board.map(|t| *t != Tile::Void))
+        .context("board doesn't fold into a cube")?;
+    walk(input, |position, direction| cube.step(position, direction))
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(22, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
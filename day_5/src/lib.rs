@@ -0,0 +1,155 @@
+use anyhow::{bail, Context, Result};
+use solve::FromStr;
+
+#[derive(FromStr)]
+#[format("move {amount} from {from} to {to}")]
+struct Instruction {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+struct Input {
+    stacks: Vec<Vec<u8>>,
+    instructions: Vec<Instruction>,
+}
+
+impl solve::Input for Input {
+    fn parse<R: std::io::BufRead>(reader: R) -> Result<Self> {
+        let mut lines = reader.lines();
+
+        let mut stacks = Vec::new();
+        'parse_stacks: loop {
+            let line = lines.next().context("Expected stack line")??;
+            let bytes = line.as_bytes();
+
+            let stack_count = (bytes.len() + 1) / 4;
+            for _ in stacks.len()..stack_count {
+                stacks.push(Vec::new());
+            }
+
+            for i in 0..stack_count {
+                let segment = &bytes[4 * i..4 * i + 3];
+                if segment[0] == b'[' {
+                    stacks[i].push(segment[1]);
+                } else if segment[1] != b' ' {
+                    break 'parse_stacks;
+                }
+            }
+        }
+
+        for stack in stacks.iter_mut() {
+            stack.reverse();
+        }
+
+        let separator = lines
+            .next()
+            .context("Expected empty line between stacks and instructions")??;
+        if !separator.is_empty() {
+            bail!("Expected empty line between stacks and instructions, found `{separator}`");
+        }
+
+        let mut instructions = Vec::new();
+
+        for line in lines {
+            let instruction: Instruction = line?.parse()?;
+            for stack in [instruction.from, instruction.to] {
+                if !(1..=stacks.len()).contains(&stack) {
+                    bail!("There is no stack {stack}, only 1 to {}", stacks.len());
+                }
+            }
+            instructions.push(instruction);
+        }
+
+        Ok(Self {
+            stacks,
+            instructions,
+        })
+    }
+}
+
+/// Takes the crates `instruction` moves off the top of their stack, in the
+/// order they were stacked in.
+fn take(stacks: &mut [Vec<u8>], instruction: &Instruction) -> Result<Vec<u8>> {
+    let from = &mut stacks[instruction.from - 1];
+    let rest = from
+        .len()
+        .checked_sub(instruction.amount)
+        .with_context(|| {
+            format!(
+                "Can't move {} crates from stack {}, which has {}",
+                instruction.amount,
+                instruction.from,
+                from.len()
+            )
+        })?;
+    Ok(from.split_off(rest))
+}
+
+/// The crate on top of each stack.
+fn tops(stacks: &[Vec<u8>]) -> Result<String> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            let top = stack
+                .last()
+                .with_context(|| format!("Stack {} is empty", i + 1))?;
+            Ok(*top as char)
+        })
+        .collect()
+}
+
+fn solve_part_one(input: &Input) -> Result<String> {
+    let mut stacks = input.stacks.clone();
+
+    for instruction in input.instructions.iter() {
+        // One crate at a time, so the moved crates end up reversed
+        let moved = take(&mut stacks, instruction)?;
+        stacks[instruction.to - 1].extend(moved.into_iter().rev());
+    }
+
+    tops(&stacks)
+}
+
+fn solve_part_two(input: &Input) -> Result<String> {
+    let mut stacks = input.stacks.clone();
+
+    for instruction in input.instructions.iter() {
+        let moved = take(&mut stacks, instruction)?;
+        stacks[instruction.to - 1].extend(moved);
+    }
+
+    tops(&stacks)
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(5, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instruction;
+
+    solve::examples! {
+        crate::day(),
+        answers,
+        #[should_panic(expected = "part one failed: Can't move 2 crates from stack 1, which has 1")]
+        too_many: text "[A]\n 1 \n\nmove 2 from 1 to 1\n" => ("A", _),
+        #[should_panic(expected = "There is no stack 2, only 1 to 1")]
+        missing_stack: text "[A]\n 1 \n\nmove 1 from 1 to 2\n" => ("A", _),
+    }
+
+    #[test]
+    fn test_instruction_error() {
+        let error = "move 1 form 2 to 3".parse::<Instruction>().err().unwrap();
+        assert_eq!(error.message(), "expected \" from \" after `amount`");
+
+        let error = "move x from 2 to 3".parse::<Instruction>().err().unwrap();
+        assert_eq!(
+            error.message(),
+            "invalid `amount`: invalid digit found in string"
+        );
+        assert_eq!((error.line(), error.column()), (1, 6));
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+
+fn first_disjoint_substring(input: &[u8], window: usize) -> Option<usize> {
+    let mut counts = [0; 26];
+    let mut unique = 0;
+
+    for n in 0..input.len() {
+        let i = (input[n] - b'a') as usize;
+        if counts[i] == 0 {
+            unique += 1;
+        }
+        counts[i] += 1;
+        if n >= window {
+            let o = (input[n - window] - b'a') as usize;
+            if counts[o] == 1 {
+                unique -= 1;
+            }
+            counts[o] -= 1;
+        }
+        if unique == window {
+            return Some(n + 1);
+        }
+    }
+
+    None
+}
+
+fn solve_part_one(input: &String) -> Result<usize> {
+    first_disjoint_substring(input.as_bytes(), 4).context("no start-of-packet marker found")
+}
+
+fn solve_part_two(input: &String) -> Result<usize> {
+    first_disjoint_substring(input.as_bytes(), 14).context("no start-of-message marker found")
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(6, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
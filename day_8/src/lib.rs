@@ -0,0 +1,131 @@
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+};
+
+use anyhow::{anyhow, Result};
+use solve::Grid;
+
+struct Input {
+    heights: Grid<u8>,
+}
+
+impl solve::Input for Input {
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let heights = Grid::parse_with(reader, |c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| anyhow!("expected a digit"))
+        })?;
+        Ok(Self { heights })
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Visibility {
+    north: u8,
+    south: u8,
+    east: u8,
+    west: u8,
+}
+
+impl Visibility {
+    fn min(&self) -> u8 {
+        u8::min(
+            u8::min(self.north, self.south),
+            u8::min(self.east, self.west),
+        )
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.min())
+    }
+}
+
+fn solve_part_one(input: &Input) -> usize {
+    let input = &input.heights;
+    let width = input.width();
+    let height = input.height();
+
+    let mut visibility = Grid::new(width, height, Visibility::default());
+
+    // north / south
+    for x in 0..width {
+        visibility[(x, 0)].north = 0;
+        visibility[(x, height - 1)].south = 0;
+    }
+    for y in 1..height {
+        for x in 0..width {
+            visibility[(x, y)].north = u8::max(input[(x, y - 1)], visibility[(x, y - 1)].north);
+            visibility[(x, height - y - 1)].south =
+                u8::max(input[(x, height - y)], visibility[(x, height - y)].south);
+        }
+    }
+    // east / west
+    for y in 0..height {
+        visibility[(0, y)].west = 0;
+        visibility[(width - 1, y)].east = 0;
+    }
+    for x in 1..width {
+        for y in 0..height {
+            visibility[(x, y)].west = u8::max(input[(x - 1, y)], visibility[(x - 1, y)].west);
+            visibility[(width - x - 1, y)].east =
+                u8::max(input[(width - x, y)], visibility[(width - x, y)].east);
+        }
+    }
+
+    let mut visible = 2 * width + 2 * height - 4;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            if input[(x, y)] > visibility[(x, y)].min() {
+                visible += 1;
+            }
+        }
+    }
+    visible
+}
+
+fn solve_part_two(input: &Input) -> usize {
+    let input = &input.heights;
+    fn score_dir(input: &Grid<u8>, mut x: usize, mut y: usize, dx: isize, dy: isize) -> usize {
+        let cap = input[(x, y)];
+        let mut count = 0;
+        while let Some(next) = input.offset(x, y, dx, dy) {
+            count += 1;
+            if input[next] >= cap {
+                break;
+            }
+            (x, y) = next;
+        }
+
+        count
+    }
+
+    fn score(input: &Grid<u8>, x: usize, y: usize) -> usize {
+        let east = score_dir(input, x, y, 1, 0);
+        let west = score_dir(input, x, y, -1, 0);
+        let south = score_dir(input, x, y, 0, 1);
+        let north = score_dir(input, x, y, 0, -1);
+        east * west * south * north
+    }
+
+    let mut max_score = 0;
+    for (x, y) in input.positions() {
+        max_score = usize::max(max_score, score(input, x, y));
+    }
+    max_score
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(8, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
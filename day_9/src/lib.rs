@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use solve::{
+    geom::{Direction, Vec2i},
+    viz::{self, Cell, Color, Frame},
+    FromStr,
+};
+
+#[derive(FromStr)]
+#[format("{direction} {amount}")]
+struct Instruction {
+    direction: Direction,
+    amount: usize,
+}
+
+struct Rope<const N: usize> {
+    knots: [Vec2i; N],
+}
+
+impl<const N: usize> Default for Rope<N> {
+    fn default() -> Self {
+        Self {
+            knots: [Vec2i::ZERO; N],
+        }
+    }
+}
+
+impl<const N: usize> Rope<N> {
+    fn move_head(&mut self, direction: Direction) {
+        self.knots[0] += direction.to_vec();
+
+        for i in 1..N {
+            let delta = self.knots[i - 1] - self.knots[i];
+            if delta.chebyshev(Vec2i::ZERO) > 1 {
+                self.knots[i] += delta.signum();
+            }
+        }
+    }
+
+    fn tail(&self) -> Vec2i {
+        self.knots[N - 1]
+    }
+}
+
+/// Draws where the tail has been and where each knot is now, labelled as in
+/// the puzzle.
+fn frame<const N: usize>(rope: &Rope<N>, visited: &HashSet<Vec2i>) -> Frame {
+    let mut frame = Frame::new()
+        .points(visited, Cell::new('#', Color::Gray))
+        .cell(Vec2i::ZERO, Cell::plain('s'));
+    // Knots further along are covered by the ones ahead of them
+    for (i, knot) in rope.knots.iter().enumerate().rev() {
+        let cell = match i {
+            0 => Cell::new('H', Color::Red),
+            _ if N == 2 => Cell::new('T', Color::Yellow),
+            _ => Cell::new(char::from_digit(i as u32, 10).unwrap_or('*'), Color::Yellow),
+        };
+        frame = frame.cell(knot, cell);
+    }
+    frame.caption(format!("{} positions visited by the tail", visited.len()))
+}
+
+fn simulate<const N: usize>(instructions: &[Instruction]) -> usize {
+    let mut rope = Rope::<N>::default();
+    let mut visited = HashSet::new();
+
+    visited.insert(rope.tail());
+
+    for instruction in instructions {
+        for _ in 0..instruction.amount {
+            rope.move_head(instruction.direction);
+            visited.insert(rope.tail());
+            viz::show(|| frame(&rope, &visited));
+        }
+    }
+
+    visited.len()
+}
+
+fn solve_part_one(input: &Vec<Instruction>) -> usize {
+    simulate::<2>(input.as_slice())
+}
+
+fn solve_part_two(input: &Vec<Instruction>) -> usize {
+    simulate::<10>(input.as_slice())
+}
+
+pub fn day() -> solve::Day {
+    solve::Day::new(9, solve_part_one, solve_part_two)
+}
+
+#[cfg(test)]
+mod tests {
+    solve::examples! {
+        crate::day(),
+        answers,
+    }
+}
@@ -0,0 +1,198 @@
+//! Counting heap allocations.
+//!
+//! Memory is only tracked in binaries that install [`CountingAllocator`]:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: solve::alloc::CountingAllocator = solve::alloc::CountingAllocator;
+//! ```
+//!
+//! `aoc` only does so with its `alloc-stats` feature, since counting slows
+//! down every allocation and so skews the timings it reports. Without it,
+//! memory is left out of the text output and is `null` or empty in JSON and
+//! CSV.
+//!
+//! The counters are shared by the whole process rather than kept per thread,
+//! so a measurement includes allocations made by every thread while it was
+//! taken. They only make sense while a single part is running: the runner
+//! leaves memory out when days run in parallel with `--jobs`, or while a part
+//! that timed out is still running (see [`cancel::abandoned`]).
+//!
+//! [`cancel::abandoned`]: crate::cancel::abandoned
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display},
+    hint,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting every allocation made through it.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Counts an allocation of `allocated` bytes that replaces `freed` bytes,
+    /// as a realloc does.
+    fn record(&self, allocated: usize, freed: usize) {
+        ALLOCATED.fetch_add(allocated as u64, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        if allocated >= freed {
+            let grown = (allocated - freed) as u64;
+            let live = LIVE.fetch_add(grown, Ordering::Relaxed) + grown;
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        } else {
+            LIVE.fetch_sub((freed - allocated) as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record(new_size, layout.size());
+        }
+        new_ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Whether [`CountingAllocator`] is the global allocator.
+pub fn is_installed() -> bool {
+    // Checked once rather than flagged on every allocation, to keep writes to
+    // shared state off the allocation path
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+    *INSTALLED.get_or_init(|| {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        drop(hint::black_box(Box::new(0u8)));
+        ALLOCATIONS.load(Ordering::Relaxed) != allocations
+    })
+}
+
+/// Heap usage over some stretch of work.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Memory {
+    /// Total bytes allocated, including ones freed again.
+    pub allocated: u64,
+    pub allocations: u64,
+    /// The most bytes live at once, beyond those live when the work started.
+    pub peak: u64,
+}
+
+impl Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} peak, {} in {} allocations",
+            format_bytes(self.peak),
+            format_bytes(self.allocated),
+            self.allocations
+        )
+    }
+}
+
+/// Calls `f`, also returning its heap usage if allocations are being counted.
+/// Allocations made on other threads while `f` runs are included too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Memory>) {
+    let measurement = Measurement::start();
+    let result = f();
+    (result, measurement.finish())
+}
+
+/// Heap usage being measured from some point on, for work whose results
+/// can't be returned from a closure passed to [`measure`], like an input
+/// that borrows from a buffer opened along with it.
+pub struct Measurement(Option<Start>);
+
+struct Start {
+    allocated: u64,
+    allocations: u64,
+    live: u64,
+}
+
+impl Measurement {
+    pub fn start() -> Self {
+        if !is_installed() {
+            return Self(None);
+        }
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        Self(Some(Start {
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            live,
+        }))
+    }
+
+    /// The heap usage since the measurement started, if allocations are
+    /// being counted.
+    pub fn finish(self) -> Option<Memory> {
+        let start = self.0?;
+        Some(Memory {
+            allocated: ALLOCATED.load(Ordering::Relaxed) - start.allocated,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - start.allocations,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(start.live),
+        })
+    }
+}
+
+/// Formats a number of bytes with a binary unit, like `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingAllocator, LIVE, PEAK};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_realloc_peak() {
+        // The allocator isn't installed in tests, so nothing else counts
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        CountingAllocator.record(100, 0);
+        CountingAllocator.record(150, 100);
+        CountingAllocator.record(50, 150);
+        assert_eq!(PEAK.load(Ordering::Relaxed) - live, 150);
+        assert_eq!(LIVE.load(Ordering::Relaxed) - live, 50);
+    }
+}
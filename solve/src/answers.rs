@@ -0,0 +1,217 @@
+use crate::{params, Day, Input, Part, Solution};
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// The checked-in answers file used when none is given explicitly, relative to
+/// the workspace root.
+pub const DEFAULT_ANSWERS: &str = "answers.txt";
+
+/// Expected answers keyed by day, input file name and part.
+///
+/// Each non-empty line that doesn't start with `#` has the form
+/// `<day> <part> <input> <answer>`, where the answer is the rest of the line.
+/// Multi-line answers escape newlines as `\n` and backslashes as `\\`.
+///
+/// Inputs that need different parameters than the day's defaults, like
+/// examples that cover a smaller area than the real puzzle, list them after
+/// the name as `test.input[row=10,search_space=20]`. They're used whenever
+/// that input is solved, and every answer for the input must list the same
+/// ones.
+#[derive(Default)]
+pub struct Answers {
+    expected: HashMap<(u32, String, Part), String>,
+    params: HashMap<(u32, String), Vec<(String, String)>>,
+}
+
+fn unescape(s: &str) -> Result<String> {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('\\') => result.push('\\'),
+                Some(x) => bail!("unknown escape sequence `\\{x}`"),
+                None => bail!("unterminated escape sequence"),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    Ok(result)
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = BufReader::new(File::open(path).context("Failed to open answers file")?);
+        Input::parse(file).context("Failed to parse answers file")
+    }
+
+    /// Loads the default answers file, or no answers if it doesn't exist.
+    pub fn load_default() -> Result<Self> {
+        let path = Path::new(DEFAULT_ANSWERS);
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn get(&self, day: u32, input: &str, part: Part) -> Option<&str> {
+        self.expected
+            .get(&(day, input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// The parameters `input` is solved with on `day`, in place of the
+    /// day's defaults.
+    pub fn params(&self, day: u32, input: &str) -> &[(String, String)] {
+        self.params
+            .get(&(day, input.to_string()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The overrides to solve `input` on `day` with: the input's own
+    /// parameters, then `given` on top of them. Also returns whether the
+    /// input's answers still hold, which they don't once `given` changes one
+    /// of the day's parameters from what they were found with.
+    pub fn overrides(
+        &self,
+        day: &Day,
+        input: &str,
+        given: &[(String, String)],
+    ) -> (Vec<(String, String)>, bool) {
+        let own = self.params(day.number(), input);
+        let holds = day.param_specs().iter().all(|spec| {
+            let Some((_, value)) = given.iter().rev().find(|(name, _)| name == spec.name()) else {
+                return true;
+            };
+            let expected = own
+                .iter()
+                .rev()
+                .find(|(name, _)| name == spec.name())
+                .map_or(spec.default(), |(_, value)| value.as_str());
+            value == expected
+        });
+        (own.iter().chain(given).cloned().collect(), holds)
+    }
+
+    pub fn has_day(&self, day: u32) -> bool {
+        self.expected.keys().any(|(d, _, _)| *d == day)
+    }
+
+    /// The names of the inputs there are answers for on `day`, in order.
+    pub fn inputs(&self, day: u32) -> Vec<&str> {
+        let mut inputs = self
+            .expected
+            .keys()
+            .filter(|(d, _, _)| *d == day)
+            .map(|(_, input, _)| input.as_str())
+            .collect::<Vec<_>>();
+        inputs.sort_unstable();
+        inputs.dedup();
+        inputs
+    }
+
+    /// Records whether the solution matches the expected answer, if there is
+    /// one.
+    pub fn check<T: Display>(&self, day: u32, input: &str, part: Part, solution: &mut Solution<T>) {
+        if let Some(expected) = self.get(day, input, part) {
+            solution.check(expected);
+        }
+    }
+}
+
+/// Splits an input field like `test.input[row=10]` into the input's name and
+/// its parameters.
+fn parse_input(field: &str) -> Result<(String, Vec<(String, String)>)> {
+    let Some((name, params)) = field.split_once('[') else {
+        return Ok((field.to_string(), Vec::new()));
+    };
+    let params = params
+        .strip_suffix(']')
+        .context("expected `]` after the input's parameters")?;
+    let params = params
+        .split(',')
+        .map(params::parse_assignment)
+        .collect::<Result<_>>()?;
+    Ok((name.to_string(), params))
+}
+
+impl Input for Answers {
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let mut expected = HashMap::new();
+        let mut all_params = HashMap::new();
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parse_line = || -> Result<_> {
+                let mut pieces = line.splitn(4, ' ');
+                let day = pieces.next().context("expected day")?.parse()?;
+                let part = pieces.next().context("expected part")?.parse()?;
+                let (input, params) = parse_input(pieces.next().context("expected input")?)?;
+                let answer = unescape(pieces.next().context("expected answer")?)?;
+                match all_params.insert((day, input.clone()), params.clone()) {
+                    Some(previous) if previous != params => bail!(
+                        "`{input}` was given different parameters on an earlier line for day {day}"
+                    ),
+                    _ => (),
+                }
+                Ok(((day, input, part), answer))
+            };
+            let (key, answer) = parse_line()
+                .with_context(|| format!("Failed to parse line {}", line_number + 1))?;
+            expected.insert(key, answer);
+        }
+        all_params.retain(|_, params: &mut Vec<_>| !params.is_empty());
+        Ok(Self {
+            expected,
+            params: all_params,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+    use crate::{Day, Input, Part};
+
+    fn day() -> Day {
+        Day::with_params(15, |_: &String, _| 0, |_: &String, _| 0).param("row", 2_000_000)
+    }
+
+    #[test]
+    fn test_params() {
+        let answers = Answers::parse(
+            "15 1 test.input[row=10] 26\n15 2 test.input[row=10] 56000011\n".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(answers.get(15, "test.input", Part::One), Some("26"));
+        let row = [("row".to_string(), "10".to_string())];
+        assert_eq!(answers.params(15, "test.input"), row);
+        assert!(answers.params(15, "other.input").is_empty());
+
+        let day = day();
+        assert_eq!(
+            answers.overrides(&day, "test.input", &[]),
+            (row.to_vec(), true)
+        );
+        let given = [("row".to_string(), "11".to_string())];
+        assert!(!answers.overrides(&day, "test.input", &given).1);
+        assert!(!answers.overrides(&day, "other.input", &given).1);
+        let given = [("row".to_string(), "2000000".to_string())];
+        assert!(answers.overrides(&day, "other.input", &given).1);
+
+        let conflicting = "15 1 test.input[row=10] 26\n15 2 test.input 56000011\n";
+        assert!(Answers::parse(conflicting.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    input_name,
+    runner::{format_duration, print_table, Options},
+    Day, Registry,
+};
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    hint::black_box,
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+/// How many times to run each part when benchmarking.
+#[derive(Clone, Copy)]
+pub struct BenchOptions {
+    /// Untimed runs made before sampling so caches and branch predictors settle
+    pub warmup: usize,
+    pub samples: usize,
+    /// Only time parsing, for inputs too large to solve in reasonable time
+    pub parse_only: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            samples: 20,
+            parse_only: false,
+        }
+    }
+}
+
+/// Runs `f` repeatedly and records how long each run took.
+pub fn measure<T>(options: &BenchOptions, mut f: impl FnMut() -> T) -> Samples {
+    for _ in 0..options.warmup {
+        black_box(f());
+    }
+    let durations = (0..options.samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Samples { durations }
+}
+
+pub struct Samples {
+    durations: Vec<Duration>,
+}
+
+/// Timings for each stage of a day.
+pub struct DaySamples {
+    /// Parsing the input as a stream with [`Input::parse`](crate::Input::parse),
+    /// or `None` for inputs that borrow from their buffer and can't be.
+    pub parse_stream: Option<Samples>,
+    /// Parsing the whole input at once with
+    /// [`Input::parse_bytes`](crate::Input::parse_bytes) or
+    /// [`InputRef::parse_ref`](crate::InputRef::parse_ref), as the runner does.
+    pub parse: Samples,
+    /// `None` when only parsing was timed.
+    pub part_one: Option<Samples>,
+    pub part_two: Option<Samples>,
+}
+
+impl DaySamples {
+    /// Each stage's samples, labelled as they are in baselines.
+    fn stages(self) -> impl Iterator<Item = (&'static str, Samples)> {
+        [
+            ("parse-stream", self.parse_stream),
+            ("parse", Some(self.parse)),
+            ("one", self.part_one),
+            ("two", self.part_two),
+        ]
+        .into_iter()
+        .filter_map(|(stage, samples)| Some((stage, samples?)))
+    }
+}
+
+impl Samples {
+    pub fn summary(&self) -> Summary {
+        let mut seconds = self
+            .durations
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        seconds.sort_by(f64::total_cmp);
+
+        let count = seconds.len();
+        let mean = seconds.iter().sum::<f64>() / count as f64;
+        let variance = if count > 1 {
+            seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+        let median = if count % 2 == 0 {
+            (seconds[count / 2 - 1] + seconds[count / 2]) / 2.0
+        } else {
+            seconds[count / 2]
+        };
+
+        Summary {
+            samples: count,
+            min: seconds[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Sample statistics, in seconds.
+#[derive(Clone, Copy, Debug)]
+pub struct Summary {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+/// Two-sided 5% critical values of Student's t distribution for 1 through 30
+/// degrees of freedom.
+const T_CRITICAL: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Change {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+impl Summary {
+    /// Compares this summary against a baseline with Welch's t-test at the 5%
+    /// significance level.
+    ///
+    /// Runs with a single sample have no spread to test against, so they're
+    /// never reported as a change.
+    pub fn compare(&self, baseline: &Summary) -> Change {
+        if self.samples < 2 || baseline.samples < 2 {
+            return Change::NoChange;
+        }
+        let var_a = self.stddev.powi(2) / self.samples as f64;
+        let var_b = baseline.stddev.powi(2) / baseline.samples as f64;
+        let standard_error = (var_a + var_b).sqrt();
+        if standard_error == 0.0 {
+            return Change::NoChange;
+        }
+
+        let t = (self.mean - baseline.mean) / standard_error;
+        let degrees_of_freedom = (var_a + var_b).powi(2)
+            / (var_a.powi(2) / (self.samples as f64 - 1.0).max(1.0)
+                + var_b.powi(2) / (baseline.samples as f64 - 1.0).max(1.0));
+        let critical = T_CRITICAL
+            .get((degrees_of_freedom.floor() as usize).max(1) - 1)
+            .copied()
+            .unwrap_or(1.960);
+
+        if t > critical {
+            Change::Regressed
+        } else if t < -critical {
+            Change::Improved
+        } else {
+            Change::NoChange
+        }
+    }
+}
+
+type BaselineKey = (u32, String, String);
+
+/// Saved benchmark summaries, one per line as
+/// `<day> <stage> <samples> <min> <median> <mean> <stddev> <input>`, where
+/// the stage is `parse-stream`, `parse`, `one` or `two`. The input name comes
+/// last since it may contain spaces.
+#[derive(Default)]
+pub struct Baseline {
+    summaries: HashMap<BaselineKey, Summary>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).context("Failed to read baseline file")?;
+        let mut summaries = HashMap::new();
+        for (line_number, line) in contents.lines().enumerate() {
+            let parse_line = || -> Result<_> {
+                let pieces = line.splitn(8, ' ').collect::<Vec<_>>();
+                if pieces.len() != 8 {
+                    bail!("expected 8 fields, found {}", pieces.len());
+                }
+                let key = (
+                    pieces[0].parse()?,
+                    pieces[1].to_string(),
+                    pieces[7].to_string(),
+                );
+                let summary = Summary {
+                    samples: pieces[2].parse()?,
+                    min: pieces[3].parse()?,
+                    median: pieces[4].parse()?,
+                    mean: pieces[5].parse()?,
+                    stddev: pieces[6].parse()?,
+                };
+                Ok((key, summary))
+            };
+            let (key, summary) = parse_line()
+                .with_context(|| format!("Failed to parse baseline line {}", line_number + 1))?;
+            summaries.insert(key, summary);
+        }
+        Ok(Self { summaries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut entries = self.summaries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| *key);
+
+        let mut contents = String::new();
+        for ((day, stage, input), s) in entries {
+            writeln!(
+                contents,
+                "{day} {stage} {} {} {} {} {} {input}",
+                s.samples, s.min, s.median, s.mean, s.stddev
+            )?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents).context("Failed to write baseline file")
+    }
+
+    pub fn get(&self, day: u32, stage: &str, input: &str) -> Option<&Summary> {
+        self.summaries
+            .get(&(day, stage.to_string(), input.to_string()))
+    }
+
+    pub fn insert(&mut self, day: u32, stage: &str, input: &str, summary: Summary) {
+        self.summaries
+            .insert((day, stage.to_string(), input.to_string()), summary);
+    }
+}
+
+fn format_seconds(seconds: f64) -> String {
+    format_duration(Duration::from_secs_f64(seconds))
+}
+
+fn format_throughput(bytes: u64, seconds: f64) -> String {
+    const UNITS: [&str; 4] = ["B/s", "KiB/s", "MiB/s", "GiB/s"];
+    let mut rate = bytes as f64 / seconds;
+    let mut unit = 0;
+    while rate >= 1024.0 && unit < UNITS.len() - 1 {
+        rate /= 1024.0;
+        unit += 1;
+    }
+    format!("{rate:.1} {}", UNITS[unit])
+}
+
+/// Benchmarks the days selected on the command line, optionally comparing
+/// against and saving a baseline.
+pub(crate) fn main(registry: &Registry, options: &Options) -> ExitCode {
+    let baseline = match options
+        .baseline
+        .as_deref()
+        .map(Path::new)
+        .map(Baseline::load)
+    {
+        Some(Ok(baseline)) => Some(baseline),
+        Some(Err(e)) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+
+    let answers = match options.answers() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = match options.runs(registry) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut header = vec![
+        "Day",
+        "Input",
+        "Stage",
+        "Min",
+        "Median",
+        "Mean",
+        "Std dev",
+        "Throughput",
+    ];
+    if baseline.is_some() {
+        header.push("Change");
+    }
+
+    let mut rows = Vec::new();
+    let mut results = Baseline::default();
+    let mut failed = false;
+
+    for (number, path) in runs {
+        let day = registry.get(number).unwrap();
+        let input = input_name(&path);
+        let (overrides, _) = answers.overrides(day, &input, &options.params);
+
+        let samples = bench_day(day, &path, &overrides, &options.bench.unwrap_or_default());
+        let (samples, input_size) = match samples {
+            Ok(samples) => samples,
+            Err(e) => {
+                eprintln!("Day {number} ({input}) failed: {e:#}");
+                failed = true;
+                continue;
+            }
+        };
+
+        for (stage, samples) in samples.stages() {
+            let summary = samples.summary();
+            let mut row = vec![
+                number.to_string(),
+                input.clone(),
+                stage.to_string(),
+                format_seconds(summary.min),
+                format_seconds(summary.median),
+                format_seconds(summary.mean),
+                format_seconds(summary.stddev),
+                format_throughput(input_size, summary.mean),
+            ];
+            if let Some(baseline) = &baseline {
+                row.push(match baseline.get(number, stage, &input) {
+                    Some(previous) => {
+                        let percent = (summary.mean / previous.mean - 1.0) * 100.0;
+                        let change = summary.compare(previous);
+                        if change == Change::Regressed {
+                            failed = true;
+                        }
+                        let label = match change {
+                            Change::Improved => "improved",
+                            Change::Regressed => "REGRESSED",
+                            Change::NoChange => "no change",
+                        };
+                        format!("{percent:+.1}% ({label})")
+                    }
+                    None => "no baseline".to_string(),
+                });
+            }
+            rows.push(row);
+            results.insert(number, stage, &input, summary);
+        }
+    }
+
+    print_table(&header, &rows);
+
+    if let Some(path) = &options.save_baseline {
+        if let Err(e) = results.save(Path::new(path)) {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+        println!("\nSaved baseline to {path}");
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_day(
+    day: &Day,
+    path: &Path,
+    overrides: &[(String, String)],
+    options: &BenchOptions,
+) -> Result<(DaySamples, u64)> {
+    let input_size = fs::metadata(path)
+        .context("Failed to open input file")?
+        .len();
+    let params = day.params(overrides)?;
+    Ok((day.bench(path, &params, options)?, input_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Baseline, Change, Samples, Summary};
+    use std::{env, fs, process, time::Duration};
+
+    #[test]
+    fn test_summary() {
+        let samples = Samples {
+            durations: [4, 1, 3, 2].map(Duration::from_secs).to_vec(),
+        };
+        let summary = samples.summary();
+        assert_eq!(summary.samples, 4);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.median, 2.5);
+        assert_eq!(summary.mean, 2.5);
+        assert!((summary.stddev - 1.2910).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Summary {
+            samples: 20,
+            min: 0.9,
+            median: 1.0,
+            mean: 1.0,
+            stddev: 0.05,
+        };
+        let noisy = Summary {
+            mean: 1.01,
+            ..baseline
+        };
+        let slower = Summary {
+            mean: 1.2,
+            ..baseline
+        };
+        let faster = Summary {
+            mean: 0.8,
+            ..baseline
+        };
+        assert_eq!(noisy.compare(&baseline), Change::NoChange);
+        assert_eq!(slower.compare(&baseline), Change::Regressed);
+        assert_eq!(faster.compare(&baseline), Change::Improved);
+    }
+
+    #[test]
+    fn test_compare_single_sample() {
+        let baseline = Summary {
+            samples: 1,
+            min: 1.0,
+            median: 1.0,
+            mean: 1.0,
+            stddev: 0.0,
+        };
+        let slower = Summary {
+            mean: 1.001,
+            ..baseline
+        };
+        assert_eq!(slower.compare(&baseline), Change::NoChange);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let summary = Summary {
+            samples: 20,
+            min: 0.5,
+            median: 1.0,
+            mean: 1.25,
+            stddev: 0.125,
+        };
+        let mut baseline = Baseline::default();
+        baseline.insert(6, "one", "test 2", summary);
+
+        let path = env::temp_dir().join(format!("baseline-{}", process::id()));
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        let summary = loaded.get(6, "one", "test 2").unwrap();
+        assert_eq!((summary.samples, summary.mean), (20, 1.25));
+    }
+}
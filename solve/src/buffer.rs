@@ -0,0 +1,113 @@
+//! Reading whole inputs into memory so they can be parsed from a byte slice.
+//!
+//! [`Input::parse_bytes`](crate::Input::parse_bytes) is handed the whole
+//! input at once, so implementations can borrow `&str` lines straight out of
+//! it with [`lines`] rather than building an owned `String` for each one.
+//! An [`InputRef`](crate::InputRef) goes further and keeps borrowing from the
+//! buffer, which the runner holds on to until both parts have run. With the
+//! `mmap` feature, large inputs are memory-mapped rather than read.
+
+use anyhow::{Context, Result};
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::Read,
+    ops::Deref,
+    path::Path,
+    str,
+};
+
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+
+/// Inputs at least this large are memory-mapped when the `mmap` feature is
+/// enabled. Mapping small files costs more than reading them.
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD: u64 = 1 << 20;
+
+/// The context of errors from opening or reading an input file, so that they
+/// can be told apart from errors parsing it.
+#[derive(Debug)]
+pub(crate) struct ReadError(&'static str);
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// The contents of an input file.
+pub enum Buffer {
+    Owned(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(Mmap),
+}
+
+impl Buffer {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut file = File::open(path).context(ReadError("Failed to open input file"))?;
+        let len = file.metadata().map_or(0, |metadata| metadata.len());
+
+        #[cfg(feature = "mmap")]
+        if len >= MMAP_THRESHOLD {
+            // Safety: inputs aren't expected to change while a day is running.
+            // If one does, the worst case is a confusing parse error.
+            let map = unsafe { Mmap::map(&file) }.context(ReadError("Failed to map input file"))?;
+            return Ok(Self::Mapped(map));
+        }
+
+        let mut bytes = Vec::with_capacity(len as usize);
+        file.read_to_end(&mut bytes)
+            .context(ReadError("Failed to read input file"))?;
+        Ok(Self::Owned(bytes))
+    }
+}
+
+impl Deref for Buffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+/// The lines of `bytes` with their line numbers, borrowed from it. Lines end
+/// at `\n` or `\r\n`, like [`BufRead::lines`](std::io::BufRead::lines).
+pub fn lines(bytes: &[u8]) -> impl Iterator<Item = (usize, Result<&str>)> {
+    let lines = (!bytes.is_empty()).then(|| {
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        bytes.split(|b| *b == b'\n')
+    });
+    lines.into_iter().flatten().enumerate().map(|(i, line)| {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line =
+            str::from_utf8(line).with_context(|| format!("Line {} is not valid UTF-8", i + 1));
+        (i + 1, line)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lines;
+
+    #[test]
+    fn test_lines() {
+        let collect = |bytes: &[u8]| {
+            lines(bytes)
+                .map(|(n, line)| (n, line.unwrap().to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(collect(b""), []);
+        assert_eq!(collect(b"\n"), [(1, String::new())]);
+        assert_eq!(
+            collect(b"a\r\n\nb"),
+            [(1, "a".into()), (2, String::new()), (3, "b".into())]
+        );
+        assert_eq!(collect(b"a\nb\n"), [(1, "a".into()), (2, "b".into())]);
+        assert!(lines(b"ok\n\xff\n").nth(1).unwrap().1.is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Cooperative cancellation for long-running solvers.
+//!
+//! Parts run under a time budget are given a [`CancellationToken`] that is
+//! cancelled once the budget runs out. Nothing can stop a thread from the
+//! outside, so solvers that might run for a long time should poll
+//! [`is_cancelled`] and give up early. The searches in [`crate::search`] and
+//! the workers in [`crate::par`] already do, and `par` returns [`Cancelled`]
+//! rather than the results of only some of its items.
+//!
+//! A part that doesn't is left running after it times out, taking up a core
+//! and allocating alongside whatever runs next. [`abandoned`] counts them, so
+//! that later measurements can be reported as unreliable.
+
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// A flag shared between a running part and whatever is waiting on it.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The error for work that stopped early because its token was cancelled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// How long a part is given to stop once its token has been cancelled.
+const GRACE: Duration = Duration::from_millis(100);
+
+/// Parts that timed out without stopping and are still running.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+/// How many parts timed out without stopping within a moment of being
+/// cancelled, and are still running. While any are, timings and memory
+/// measured for other parts can't be trusted.
+pub fn abandoned() -> usize {
+    ABANDONED.load(Ordering::Relaxed)
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Worker {
+    Running,
+    Finished,
+    Abandoned,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// The token for the part running on this thread, if it has one.
+pub fn current() -> Option<CancellationToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Whether the part running on this thread has been cancelled. Always false
+/// outside of [`with_timeout`].
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().is_some_and(|t| t.is_cancelled()))
+}
+
+/// Calls `f` with `token` as this thread's current token, restoring the
+/// previous one afterwards.
+pub fn with<T>(token: Option<CancellationToken>, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(token));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Calls `f` on a new thread, waiting at most `timeout` for it to finish.
+///
+/// Returns `None` if it took too long, after cancelling its token. Threads
+/// can't be stopped from the outside, so `f` should poll [`is_cancelled`] to
+/// stop promptly. If it hasn't stopped shortly after being cancelled, it's
+/// left running and counted by [`abandoned`] until it finishes on its own.
+/// Panics in `f` are resumed on the calling thread.
+pub fn with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let token = CancellationToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let worker_token = token.clone();
+    let state = Arc::new(Mutex::new(Worker::Running));
+    let worker_state = state.clone();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| with(Some(worker_token), f)));
+        let mut state = worker_state.lock().unwrap();
+        if *state == Worker::Abandoned {
+            ABANDONED.fetch_sub(1, Ordering::Relaxed);
+        }
+        *state = Worker::Finished;
+        // The receiver is gone if we already gave up waiting
+        let _ = sender.send(result);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            if receiver.recv_timeout(GRACE).is_err() {
+                let mut state = state.lock().unwrap();
+                if *state == Worker::Running {
+                    *state = Worker::Abandoned;
+                    ABANDONED.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None
+        }
+        Err(RecvTimeoutError::Disconnected) => unreachable!("the worker always sends a result"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{abandoned, is_cancelled, with_timeout};
+
+    #[test]
+    fn test_with_timeout() {
+        assert_eq!(with_timeout(Duration::from_secs(10), || 42), Some(42));
+        assert!(!is_cancelled());
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let result = with_timeout(Duration::from_millis(10), move || {
+            while !is_cancelled() {
+                std::thread::yield_now();
+            }
+            sender.send(()).unwrap();
+        });
+        assert_eq!(result, None);
+        // The solver sees the cancellation and stops
+        receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+    }
+
+    #[test]
+    fn test_abandoned() {
+        let (sender, receiver) = std::sync::mpsc::channel::<()>();
+        let result = with_timeout(Duration::from_millis(10), move || {
+            // Ignores cancellation until told to stop
+            let _ = receiver.recv();
+        });
+        assert_eq!(result, None);
+        assert_eq!(abandoned(), 1);
+
+        drop(sender);
+        let start = std::time::Instant::now();
+        while abandoned() > 0 {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::yield_now();
+        }
+    }
+}
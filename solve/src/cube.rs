@@ -0,0 +1,370 @@
+//! Folding flat cube nets into cubes.
+//!
+//! A net is a [`Grid`] mask whose set cells make up six equal square faces
+//! laid out on a grid of face-sized blocks. [`CubeNet::fold`] checks that
+//! those faces fold into a cube and works out how their edges join up, so
+//! walking off the edge of one face can continue on the face it folds against.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use crate::{
+    geom::{Direction, Vec2, Vec3},
+    Grid,
+};
+
+/// One face of a folded cube net.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Face {
+    /// The position of the face in the net, in units of whole faces.
+    pub position: Vec2<isize>,
+    /// The outward normal of the face once folded.
+    pub normal: Vec3<isize>,
+    /// The direction the face's x axis points in once folded.
+    pub right: Vec3<isize>,
+    /// The direction the face's y axis points in once folded.
+    pub down: Vec3<isize>,
+}
+
+impl Face {
+    /// The direction in 3D that moving in `direction` across the face goes.
+    pub fn axis(&self, direction: Direction) -> Vec3<isize> {
+        match direction {
+            Direction::Up => -self.down,
+            Direction::Right => self.right,
+            Direction::Down => self.down,
+            Direction::Left => -self.right,
+        }
+    }
+
+    /// The face across the edge in `direction`, after folding it over that
+    /// edge away from the normal.
+    fn fold(&self, direction: Direction) -> Self {
+        let (right, down) = match direction {
+            Direction::Up => (self.right, self.normal),
+            Direction::Right => (-self.normal, self.down),
+            Direction::Down => (self.right, -self.normal),
+            Direction::Left => (self.normal, self.down),
+        };
+        Self {
+            position: self.position + direction.to_vec(),
+            normal: self.axis(direction),
+            right,
+            down,
+        }
+    }
+}
+
+/// Where leaving a face through one of its edges leads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Transition {
+    /// The index of the face on the other side of the edge.
+    pub face: usize,
+    /// The edge of that face which is crossed to enter it.
+    pub edge: Direction,
+    /// Whether positions along the edge run in opposite directions on the two
+    /// faces. Positions along top and bottom edges count from the left, and
+    /// along left and right edges count from the top.
+    pub flipped: bool,
+}
+
+/// A net of six square faces folded into a cube.
+#[derive(Clone, Debug)]
+pub struct CubeNet {
+    size: usize,
+    faces: Vec<Face>,
+    by_position: HashMap<Vec2<isize>, usize>,
+    transitions: Vec<[Transition; 4]>,
+}
+
+impl CubeNet {
+    /// Folds the set cells of `mask` into a cube, failing if they don't form
+    /// a valid net.
+    pub fn fold(mask: &Grid<bool>) -> Result<Self> {
+        let cells = mask.iter().filter(|(_, set)| **set).count();
+        let size = (1..).find(|s| 6 * s * s >= cells).unwrap();
+        if 6 * size * size != cells {
+            bail!("{cells} cells can't make six square faces");
+        }
+
+        // Every face-sized block must be either entirely set or entirely empty
+        let mut positions = Vec::new();
+        for block_y in 0..mask.height().div_ceil(size) {
+            for block_x in 0..mask.width().div_ceil(size) {
+                let set = (0..size * size)
+                    .filter(|i| {
+                        let (x, y) = (block_x * size + i % size, block_y * size + i / size);
+                        mask.get(x, y) == Some(&true)
+                    })
+                    .count();
+                if set == size * size {
+                    positions.push(Vec2::new(block_x as isize, block_y as isize));
+                } else if set != 0 {
+                    bail!("the face at ({block_x}, {block_y}) is only partly filled");
+                }
+            }
+        }
+
+        // Walk the net from its first face, folding each face over the edge it
+        // was reached from
+        let mut faces = vec![Face {
+            position: positions[0],
+            normal: Vec3::new(0, 0, -1),
+            right: Vec3::new(1, 0, 0),
+            down: Vec3::new(0, 1, 0),
+        }];
+        let mut by_position = HashMap::from([(positions[0], 0)]);
+        let mut i = 0;
+        while i < faces.len() {
+            for direction in Direction::ALL {
+                let next = faces[i].fold(direction);
+                if positions.contains(&next.position) && !by_position.contains_key(&next.position) {
+                    by_position.insert(next.position, faces.len());
+                    faces.push(next);
+                }
+            }
+            i += 1;
+        }
+        if faces.len() != positions.len() {
+            bail!("the faces aren't all connected");
+        }
+
+        let by_normal = faces
+            .iter()
+            .enumerate()
+            .map(|(i, face)| (face.normal, i))
+            .collect::<HashMap<_, _>>();
+        if by_normal.len() != 6 {
+            bail!("faces overlap when folded");
+        }
+
+        let transitions = faces
+            .iter()
+            .map(|from| {
+                Direction::ALL.map(|direction| {
+                    let face = by_normal[&from.axis(direction)];
+                    let to = &faces[face];
+                    let edge = Direction::ALL
+                        .into_iter()
+                        .find(|d| to.axis(*d) == from.normal)
+                        .unwrap();
+                    let along = |face: &Face, edge: Direction| match edge {
+                        Direction::Up | Direction::Down => face.right,
+                        Direction::Left | Direction::Right => face.down,
+                    };
+                    Transition {
+                        face,
+                        edge,
+                        flipped: along(from, direction) != along(to, edge),
+                    }
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            size,
+            faces,
+            by_position,
+            transitions,
+        })
+    }
+
+    /// The width and height of each face in cells.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn faces(&self) -> &[Face] {
+        &self.faces
+    }
+
+    /// Returns the index of the face containing `cell`, if any.
+    pub fn face_at(&self, (x, y): (usize, usize)) -> Option<usize> {
+        let block = Vec2::new(x / self.size, y / self.size);
+        self.by_position
+            .get(&Vec2::new(block.x as isize, block.y as isize))
+            .copied()
+    }
+
+    /// Returns where leaving `face` through the edge in `direction` leads.
+    pub fn transition(&self, face: usize, direction: Direction) -> Transition {
+        self.transitions[face][direction as usize]
+    }
+
+    /// Returns the centre of `cell` on the folded cube, if it's part of a face.
+    ///
+    /// The cube spans `-size..=size` along each axis, so that cell centres
+    /// fall on integer coordinates.
+    pub fn position(&self, cell: (usize, usize)) -> Option<Vec3<isize>> {
+        let face = &self.faces[self.face_at(cell)?];
+        let size = self.size as isize;
+        let local = Vec2::new((cell.0 % self.size) as isize, (cell.1 % self.size) as isize);
+        Some(
+            face.normal * size
+                + face.right * (2 * local.x + 1 - size)
+                + face.down * (2 * local.y + 1 - size),
+        )
+    }
+
+    /// Moves one cell from `cell` in `direction`, following the cube around
+    /// any edge crossed. Returns the new cell and the direction now faced.
+    ///
+    /// Panics if `cell` isn't part of a face.
+    pub fn step(&self, cell: (usize, usize), direction: Direction) -> ((usize, usize), Direction) {
+        let face = self.face_at(cell).expect("cell is not on the cube");
+        let delta = direction.to_vec::<isize>();
+        let next = cell
+            .0
+            .checked_add_signed(delta.x)
+            .zip(cell.1.checked_add_signed(delta.y));
+        if let Some(next) = next.filter(|next| self.face_at(*next) == Some(face)) {
+            return (next, direction);
+        }
+
+        let size = self.size;
+        let offset = match direction {
+            Direction::Up | Direction::Down => cell.0 % size,
+            Direction::Left | Direction::Right => cell.1 % size,
+        };
+        let transition = self.transition(face, direction);
+        let offset = if transition.flipped {
+            size - 1 - offset
+        } else {
+            offset
+        };
+        let (x, y) = match transition.edge {
+            Direction::Up => (offset, 0),
+            Direction::Right => (size - 1, offset),
+            Direction::Down => (offset, size - 1),
+            Direction::Left => (0, offset),
+        };
+
+        // Faces in the net never have negative positions
+        let block = self.faces[transition.face].position;
+        (
+            (block.x as usize * size + x, block.y as usize * size + y),
+            transition.edge.opposite(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::CubeNet;
+    use crate::{geom::Direction, Grid};
+
+    /// The 11 distinct nets of a cube, one face per character.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...\n",
+        "#...\n####\n.#..\n",
+        "#...\n####\n..#.\n",
+        "#...\n####\n...#\n",
+        ".#..\n####\n.#..\n",
+        ".#..\n####\n..#.\n",
+        "##..\n.###\n.#..\n",
+        "##..\n.###\n..#.\n",
+        "##..\n.###\n...#\n",
+        "##..\n.##.\n..##\n",
+        "###..\n..###\n",
+    ];
+
+    fn layout(net: &str) -> Grid<bool> {
+        Grid::parse_with(net.as_bytes(), |c| Ok(c == '#')).unwrap()
+    }
+
+    /// Scales a layout up so each face is `size` cells across.
+    fn scale(layout: &Grid<bool>, size: usize) -> Grid<bool> {
+        Grid::from_fn(layout.width() * size, layout.height() * size, |x, y| {
+            layout[(x / size, y / size)]
+        })
+    }
+
+    /// All eight rotations and reflections of a layout.
+    fn symmetries(layout: Grid<bool>) -> Vec<Grid<bool>> {
+        let mut result = Vec::new();
+        let mut current = layout;
+        for _ in 0..4 {
+            result.push(current.transpose());
+            current = current.rotate_clockwise();
+            result.push(current.clone());
+        }
+        result
+    }
+
+    fn check(net: &CubeNet, mask: &Grid<bool>) {
+        // Every cell lands somewhere different on the cube
+        let cells = mask
+            .iter()
+            .filter(|(_, set)| **set)
+            .map(|(cell, _)| net.position(cell).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(cells.len(), 6 * net.size() * net.size());
+
+        for (i, face) in net.faces().iter().enumerate() {
+            for direction in Direction::ALL {
+                // Crossing an edge and back again returns to the same face
+                let there = net.transition(i, direction);
+                let back = net.transition(there.face, there.edge);
+                assert_eq!((back.face, back.edge), (i, direction));
+                assert_eq!(back.flipped, there.flipped);
+
+                // Neighbours in the net stay joined along the same edge
+                let neighbor = face.position + direction.to_vec();
+                if let Some(j) = net.faces().iter().position(|f| f.position == neighbor) {
+                    assert_eq!(there.face, j);
+                    assert_eq!(there.edge, direction.opposite());
+                    assert!(!there.flipped);
+                }
+            }
+        }
+
+        // Stepping moves between adjacent cells on the cube, and stepping
+        // back the other way undoes it
+        for (cell, _) in mask.iter().filter(|(_, set)| **set) {
+            for direction in Direction::ALL {
+                let (next, facing) = net.step(cell, direction);
+                let from = net.position(cell).unwrap();
+                let to = net.position(next).unwrap();
+                assert_eq!(from.manhattan(to), 2);
+                assert_eq!(
+                    net.step(next, facing.opposite()),
+                    (cell, direction.opposite())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_nets() {
+        for net in NETS {
+            for mask in symmetries(layout(net)) {
+                for size in [1, 3] {
+                    let mask = scale(&mask, size);
+                    let folded = CubeNet::fold(&mask).unwrap_or_else(|e| panic!("{net}: {e}"));
+                    assert_eq!(folded.size(), size);
+                    check(&folded, &mask);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_nets() {
+        for net in [
+            "###\n###\n",
+            "######\n",
+            "##..\n##..\n.##.\n",
+            "#..#\n####\n",
+        ] {
+            assert!(CubeNet::fold(&scale(&layout(net), 2)).is_err(), "{net}");
+        }
+
+        let mut partial = scale(&layout(NETS[0]), 2);
+        partial[(0, 0)] = false;
+        partial[(2, 0)] = true;
+        assert!(CubeNet::fold(&partial).is_err());
+    }
+}
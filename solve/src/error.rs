@@ -0,0 +1,178 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+/// An error that points at the place in the input where parsing failed.
+///
+/// When displayed, it renders like a compiler diagnostic with the offending
+/// line and a caret underline beneath the failing span.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    message: String,
+    path: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    len: usize,
+    snippet: String,
+}
+
+impl ParseError {
+    /// Creates an error spanning `len` characters of `snippet`, starting at the
+    /// one-based `line` and `column`.
+    pub fn new(
+        message: impl Display,
+        line: usize,
+        column: usize,
+        len: usize,
+        snippet: impl Into<String>,
+    ) -> Self {
+        Self {
+            message: message.to_string(),
+            path: None,
+            line,
+            column,
+            len,
+            snippet: snippet.into(),
+        }
+    }
+
+    /// Creates an error spanning the whole of `snippet`, which is the one-based
+    /// line `line` of the input.
+    pub fn whole_line(message: impl Display, line: usize, snippet: &str) -> Self {
+        Self::new(message, line, 1, snippet.chars().count(), snippet)
+    }
+
+    /// Creates an error spanning `len` bytes of `source` starting at byte
+    /// `offset`, working out the line and column from the source text.
+    pub fn at_offset(message: impl Display, source: &str, offset: usize, len: usize) -> Self {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        let snippet = &source[line_start..line_end];
+        let end = (offset + len).min(line_end);
+
+        Self::new(
+            message,
+            source[..line_start].matches('\n').count() + 1,
+            source[line_start..offset].chars().count() + 1,
+            source[offset..end].chars().count(),
+            snippet,
+        )
+    }
+
+    /// Moves the error to the one-based line `line`, for errors produced by
+    /// parsing a single line of a larger input.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Moves the error to where the text it was produced from sits in
+    /// `source`, for errors produced by parsing the part of `source` that
+    /// starts at byte `offset`.
+    pub fn within(mut self, source: &str, offset: usize) -> Self {
+        let start = Self::at_offset("", source, offset, 0);
+        if self.line == 1 {
+            self.column += start.column - 1;
+            self.snippet = start.snippet;
+        }
+        self.line += start.line - 1;
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn set_path(&mut self, path: impl Into<PathBuf>) {
+        self.path = Some(path.into());
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The one-based line the error occurred on.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The one-based column the error's span starts at, in characters.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The full text of the line the error occurred on.
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.message)?;
+        match &self.path {
+            Some(path) => writeln!(
+                f,
+                "{gutter}--> {}:{}:{}",
+                path.display(),
+                self.line,
+                self.column
+            )?,
+            None => writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?,
+        }
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column.saturating_sub(1)),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn test_at_offset() {
+        let source = "1,2\n3,x,5\n";
+        let error = ParseError::at_offset("invalid digit", source, 6, 1);
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 3);
+        assert_eq!(error.snippet(), "3,x,5");
+    }
+
+    #[test]
+    fn test_within() {
+        let source = "1,2\n3,4x,5\n";
+        let error = ParseError::new("invalid digit", 1, 2, 1, "4x").within(source, 6);
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 4);
+        assert_eq!(error.snippet(), "3,4x,5");
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ParseError::new("expected \"from\"", 12, 8, 4, "move 1 frm 2 to 1")
+            .with_path("day_5/test.input");
+        assert_eq!(
+            error.to_string(),
+            "expected \"from\"\n  --> day_5/test.input:12:8\n   |\n12 | move 1 frm 2 to 1\n   |        ^^^^"
+        );
+    }
+}
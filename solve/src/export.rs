@@ -0,0 +1,386 @@
+//! Saving visualisations as images, to attach a simulation to a review
+//! rather than describe it.
+//!
+//! Once [`start`] has been called, which the runner does for
+//! `--export PATH`, every frame passed to [`viz::show`](crate::viz::show)
+//! is recorded, and [`finish`] writes them all out. What's written depends
+//! on `PATH`:
+//!
+//! - `*.gif`: an animated GIF
+//! - `*.png`: an animated PNG
+//! - anything else: a directory of PNGs, one per frame
+//!
+//! Each cell becomes a square of pixels in its [`Color`](crate::viz::Color),
+//! so a day picks its palette by giving each kind of cell a colour, using
+//! [`Color::Rgb`](crate::viz::Color::Rgb) for exact ones. Frames are placed
+//! in the same coordinates across the whole animation, so that they line up,
+//! unless they're spread too far apart to fit.
+
+use crate::viz::{self, Frame};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The size in pixels of the square each cell is drawn as.
+const CELL_SIZE: usize = 4;
+
+/// Frames are lined up along an axis only if they all fit within this many
+/// cells along it. Past that the simulation is taken to be scrolling, like a
+/// tower that keeps growing, and each frame starts at the canvas edge.
+const MAX_SPAN: usize = 1000;
+
+/// The colour of cells nothing was drawn in.
+const BACKGROUND: [u8; 3] = [15, 15, 35];
+
+/// How frames are recorded and played back.
+#[derive(Clone, Copy, Debug)]
+pub struct ExportOptions {
+    /// Frames shown per second in animations, or 0 for as fast as viewers
+    /// allow.
+    pub fps: f64,
+    /// Only every this many frames are recorded, to keep long simulations
+    /// from filling memory.
+    pub every: usize,
+}
+
+impl ExportOptions {
+    /// How long each frame of an animation is shown for.
+    fn frame_seconds(&self) -> f64 {
+        if self.fps > 0.0 {
+            1.0 / self.fps
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            fps: 30.0,
+            every: 1,
+        }
+    }
+}
+
+/// A recorded frame, with a palette index for each cell of its bounding box.
+struct Recorded {
+    min: (i64, i64),
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+struct Recorder {
+    path: PathBuf,
+    options: ExportOptions,
+    /// How many frames have been shown, including ones that weren't kept.
+    shown: usize,
+    /// Every colour used so far, starting with the background. GIFs can't
+    /// have more than 256.
+    palette: Vec<[u8; 3]>,
+    frames: Vec<Recorded>,
+}
+
+impl Recorder {
+    fn palette_index(&mut self, rgb: [u8; 3]) -> u8 {
+        if let Some(index) = self.palette.iter().position(|&c| c == rgb) {
+            return index as u8;
+        }
+        if self.palette.len() < 256 {
+            self.palette.push(rgb);
+            return (self.palette.len() - 1) as u8;
+        }
+        // Out of room, so make do with the closest colour there is
+        let distance = |c: &[u8; 3]| {
+            (0..3)
+                .map(|i| (c[i] as i32 - rgb[i] as i32).pow(2))
+                .sum::<i32>()
+        };
+        (0..self.palette.len())
+            .min_by_key(|&i| distance(&self.palette[i]))
+            .unwrap() as u8
+    }
+
+    fn record(&mut self, frame: &Frame) {
+        self.shown += 1;
+        if !(self.shown - 1).is_multiple_of(self.options.every) {
+            return;
+        }
+        let cells = frame
+            .cells()
+            .filter_map(|(position, cell)| Some((position, cell.rgb()?)))
+            .collect::<Vec<_>>();
+        let (Some(min_x), Some(min_y)) = (
+            cells.iter().map(|((x, _), _)| *x).min(),
+            cells.iter().map(|((_, y), _)| *y).min(),
+        ) else {
+            return;
+        };
+        let width = (cells.iter().map(|((x, _), _)| *x).max().unwrap() - min_x + 1) as usize;
+        let height = (cells.iter().map(|((_, y), _)| *y).max().unwrap() - min_y + 1) as usize;
+
+        let mut recorded = Recorded {
+            min: (min_x, min_y),
+            width,
+            height,
+            cells: vec![0; width * height],
+        };
+        for ((x, y), rgb) in cells {
+            let index = (y - min_y) as usize * width + (x - min_x) as usize;
+            recorded.cells[index] = self.palette_index(rgb);
+        }
+        self.frames.push(recorded);
+    }
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Starts recording frames to write to `path` when [`finish`] is called.
+pub fn start(path: impl Into<PathBuf>, options: ExportOptions) {
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        path: path.into(),
+        options,
+        shown: 0,
+        palette: vec![BACKGROUND],
+        frames: Vec::new(),
+    });
+    viz::enable();
+}
+
+pub(crate) fn is_recording() -> bool {
+    RECORDER.lock().unwrap().is_some()
+}
+
+pub(crate) fn record(frame: &Frame) {
+    if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+        recorder.record(frame);
+    }
+}
+
+/// Where one axis of each frame goes on the canvas.
+struct Axis {
+    /// The coordinate at the canvas edge, or `None` if each frame starts at
+    /// the edge.
+    origin: Option<i64>,
+    /// The canvas size in cells.
+    cells: usize,
+}
+
+impl Axis {
+    fn fitting(frames: impl Iterator<Item = (i64, usize)> + Clone) -> Self {
+        let min = frames.clone().map(|(min, _)| min).min().unwrap();
+        let max = frames
+            .clone()
+            .map(|(min, len)| min + len as i64)
+            .max()
+            .unwrap();
+        if (max - min) as usize <= MAX_SPAN {
+            Self {
+                origin: Some(min),
+                cells: (max - min) as usize,
+            }
+        } else {
+            let longest = frames.map(|(_, len)| len).max().unwrap();
+            Self {
+                origin: None,
+                cells: longest.min(MAX_SPAN),
+            }
+        }
+    }
+
+    fn offset(&self, min: i64) -> usize {
+        self.origin.map_or(0, |origin| (min - origin) as usize)
+    }
+}
+
+/// The frames drawn into a shared canvas, as palette indices per pixel.
+struct Canvas {
+    x: Axis,
+    y: Axis,
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    fn fitting(frames: &[Recorded]) -> Self {
+        let x = Axis::fitting(frames.iter().map(|f| (f.min.0, f.width)));
+        let y = Axis::fitting(frames.iter().map(|f| (f.min.1, f.height)));
+        Self {
+            width: x.cells * CELL_SIZE,
+            height: y.cells * CELL_SIZE,
+            x,
+            y,
+        }
+    }
+
+    fn pixels(&self, frame: &Recorded) -> Vec<u8> {
+        let mut pixels = vec![0; self.width * self.height];
+        let left = self.x.offset(frame.min.0);
+        let top = self.y.offset(frame.min.1);
+        for (i, &index) in frame.cells.iter().enumerate() {
+            let (x, y) = (left + i % frame.width, top + i / frame.width);
+            // Frames larger than the canvas are cropped, keeping the top left
+            if x >= self.x.cells || y >= self.y.cells {
+                continue;
+            }
+            let (x, y) = (x * CELL_SIZE, y * CELL_SIZE);
+            for row in y..y + CELL_SIZE {
+                pixels[row * self.width + x..row * self.width + x + CELL_SIZE].fill(index);
+            }
+        }
+        pixels
+    }
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+fn write_gif(path: &Path, canvas: &Canvas, recorder: &Recorder) -> Result<()> {
+    let (Ok(width), Ok(height)) = (canvas.width.try_into(), canvas.height.try_into()) else {
+        bail!("frames are too large for a GIF");
+    };
+    let palette = recorder.palette.concat();
+    let mut encoder = gif::Encoder::new(create(path)?, width, height, &palette)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    // GIF delays are in hundredths of a second, and viewers slow down
+    // anything under two
+    let delay = (recorder.options.frame_seconds() * 100.0)
+        .round()
+        .clamp(2.0, 65535.0) as u16;
+    for frame in &recorder.frames {
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, canvas.pixels(frame), None);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    encoder.into_inner()?.flush()?;
+    Ok(())
+}
+
+fn png_encoder<W: Write>(w: W, canvas: &Canvas, palette: &[[u8; 3]]) -> png::Encoder<'static, W> {
+    let mut encoder = png::Encoder::new(w, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.concat());
+    encoder
+}
+
+fn write_apng(path: &Path, canvas: &Canvas, recorder: &Recorder) -> Result<()> {
+    let mut encoder = png_encoder(create(path)?, canvas, &recorder.palette);
+    encoder.set_animated(recorder.frames.len() as u32, 0)?;
+    let milliseconds = (recorder.options.frame_seconds() * 1000.0)
+        .round()
+        .clamp(1.0, 65535.0);
+    encoder.set_frame_delay(milliseconds as u16, 1000)?;
+    let mut writer = encoder.write_header()?;
+    for frame in &recorder.frames {
+        writer.write_image_data(&canvas.pixels(frame))?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn write_pngs(directory: &Path, canvas: &Canvas, recorder: &Recorder) -> Result<()> {
+    fs::create_dir_all(directory)
+        .with_context(|| format!("Failed to create {}", directory.display()))?;
+    for (i, frame) in recorder.frames.iter().enumerate() {
+        let path = directory.join(format!("frame_{:05}.png", i + 1));
+        let mut writer = png_encoder(create(&path)?, canvas, &recorder.palette).write_header()?;
+        writer.write_image_data(&canvas.pixels(frame))?;
+        writer.finish()?;
+    }
+    Ok(())
+}
+
+/// Stops recording and writes out the frames recorded so far. Returns where
+/// they were written and how many there were, or `None` if nothing was
+/// being recorded.
+pub fn finish() -> Result<Option<(PathBuf, usize)>> {
+    let Some(recorder) = RECORDER.lock().unwrap().take() else {
+        return Ok(None);
+    };
+    let path = recorder.path.as_path();
+    if recorder.frames.is_empty() {
+        bail!("none of the days that ran draw frames to export");
+    }
+    let canvas = Canvas::fitting(&recorder.frames);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("gif") => write_gif(path, &canvas, &recorder),
+        Some("png") => write_apng(path, &canvas, &recorder),
+        _ => write_pngs(path, &canvas, &recorder),
+    }
+    .with_context(|| format!("Failed to export frames to {}", path.display()))?;
+    Ok(Some((recorder.path.clone(), recorder.frames.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Canvas, Recorder, BACKGROUND, CELL_SIZE};
+    use crate::viz::{Cell, Color, Frame};
+
+    #[test]
+    fn test_record() {
+        let mut recorder = Recorder {
+            path: "test.gif".into(),
+            options: super::ExportOptions {
+                every: 2,
+                ..Default::default()
+            },
+            shown: 0,
+            palette: vec![BACKGROUND],
+            frames: Vec::new(),
+        };
+        let red = Cell::new('#', Color::Red);
+        recorder.record(
+            &Frame::new()
+                .cell((1, 1), red)
+                .cell((2, 1), Cell::plain(' ')),
+        );
+        recorder.record(&Frame::new().cell((5, 5), red));
+        recorder.record(&Frame::new().cell((0, 2), Cell::new('o', Color::Rgb(1, 2, 3))));
+
+        assert_eq!(recorder.palette, [BACKGROUND, Color::Red.rgb(), [1, 2, 3]]);
+        assert_eq!(recorder.frames.len(), 2);
+
+        let canvas = Canvas::fitting(&recorder.frames);
+        assert_eq!(
+            (canvas.width, canvas.height),
+            (2 * CELL_SIZE, 2 * CELL_SIZE)
+        );
+        let pixels = canvas.pixels(&recorder.frames[1]);
+        assert_eq!(pixels[CELL_SIZE * canvas.width], 2);
+        assert_eq!(
+            pixels.iter().filter(|&&p| p == 2).count(),
+            CELL_SIZE * CELL_SIZE
+        );
+    }
+
+    #[test]
+    fn test_scrolling() {
+        let mut recorder = Recorder {
+            path: "test.gif".into(),
+            options: Default::default(),
+            shown: 0,
+            palette: vec![BACKGROUND],
+            frames: Vec::new(),
+        };
+        let cell = Cell::plain('#');
+        recorder.record(&Frame::new().cell((0, 0), cell).cell((1, 1), cell));
+        recorder.record(&Frame::new().cell((1, -5000), cell));
+
+        // Lined up across, but each frame starts at the top
+        let canvas = Canvas::fitting(&recorder.frames);
+        assert_eq!(
+            (canvas.width, canvas.height),
+            (2 * CELL_SIZE, 2 * CELL_SIZE)
+        );
+        assert_eq!(canvas.pixels(&recorder.frames[1])[CELL_SIZE], 1);
+    }
+}
@@ -0,0 +1,516 @@
+//! Integer vectors, directions and axis-aligned boxes.
+//!
+//! Positions use screen coordinates to match [`Grid`](crate::Grid): `x` grows
+//! to the right and `y` grows downwards, so [`Direction::Up`] is `(0, -1)`.
+
+use std::{
+    fmt::{self, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{
+    parser::{self, integer, opt, preceded, spaces, Grammar, Parser},
+    ParseError,
+};
+
+/// The integer types vectors can be built from.
+pub trait Scalar:
+    Copy
+    + Ord
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn signum(self) -> Self;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_scalar {
+    (signed: $($t:ty),*; unsigned: $($u:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        })*
+        $(impl Scalar for $u {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn signum(self) -> Self {
+                Self::from(self > 0)
+            }
+        })*
+    };
+}
+
+impl_scalar!(signed: i8, i16, i32, i64, i128, isize; unsigned: u8, u16, u32, u64, u128, usize);
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+pub type Vec2i = Vec2<i32>;
+pub type Vec3i = Vec3<i32>;
+
+/// Implements component-wise arithmetic, scaling and the shared helpers for
+/// a vector type.
+macro_rules! impl_vector {
+    ($name:ident { $($field:ident),* }) => {
+        impl<T: Scalar> $name<T> {
+            pub const ZERO: Self = Self { $($field: T::ZERO),* };
+
+            pub const fn new($($field: T),*) -> Self {
+                Self { $($field),* }
+            }
+
+            /// The sum of the absolute values of the components.
+            pub fn manhattan_len(self) -> T {
+                self.manhattan(Self::ZERO)
+            }
+
+            /// The distance to `other` moving along one axis at a time.
+            pub fn manhattan(self, other: Self) -> T {
+                T::ZERO $(+ self.$field.abs_diff(other.$field))*
+            }
+
+            /// The distance to `other` when diagonal moves are allowed.
+            pub fn chebyshev(self, other: Self) -> T {
+                T::ZERO $(.max(self.$field.abs_diff(other.$field)))*
+            }
+
+            pub fn component_min(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),* }
+            }
+
+            pub fn component_max(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),* }
+            }
+
+            /// Replaces each component with its sign, giving a step of at
+            /// most one along each axis towards the original value.
+            pub fn signum(self) -> Self {
+                Self { $($field: self.$field.signum()),* }
+            }
+
+            /// The product of the components.
+            pub fn product(self) -> T {
+                T::ONE $(* self.$field)*
+            }
+        }
+
+        impl<T: Scalar> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Scalar> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl<T: Scalar> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: Scalar> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+
+        impl<T: Scalar> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Scalar> MulAssign<T> for $name<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                $(self.$field *= rhs;)*
+            }
+        }
+
+        impl<T: Scalar> Div<T> for $name<T> {
+            type Output = Self;
+
+            fn div(self, rhs: T) -> Self {
+                Self { $($field: self.$field / rhs),* }
+            }
+        }
+
+        impl<T: Scalar> Rem<T> for $name<T> {
+            type Output = Self;
+
+            fn rem(self, rhs: T) -> Self {
+                Self { $($field: self.$field % rhs),* }
+            }
+        }
+
+        impl<T: Scalar + Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: Scalar> Sum for $name<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        /// Accepts `1,2` as well as labelled forms like `x=1, y=2`.
+        impl<T> Grammar for $name<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            fn grammar<'a>() -> impl Parser<'a, Self> {
+                let separator = || (",", spaces());
+                impl_vector!(@grammar separator; $($field),*)
+            }
+        }
+
+        impl<T> FromStr for $name<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parser::from_str(s)
+            }
+        }
+
+        impl<T: Display> Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let [first, rest @ ..] = [$(&self.$field),*];
+                write!(f, "({first}")?;
+                for component in rest {
+                    write!(f, ", {component}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    };
+    (@grammar $separator:ident; $first:ident $(, $field:ident)*) => {
+        (
+            preceded(opt(concat!(stringify!($first), "=")), integer()),
+            $(preceded(
+                ($separator(), opt(concat!(stringify!($field), "="))),
+                integer(),
+            ),)*
+        )
+            .map(|($first, $($field,)*)| Self { $first, $($field),* })
+    };
+}
+
+impl_vector!(Vec2 { x, y });
+impl_vector!(Vec3 { x, y, z });
+
+impl<T: Scalar + Neg<Output = T>> Vec2<T> {
+    /// Rotates a quarter turn clockwise, as seen on screen.
+    pub fn rotate_clockwise(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates a quarter turn counter-clockwise, as seen on screen.
+    pub fn rotate_counter_clockwise(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(v: Vec3<T>) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+/// One of the four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn to_vec<T: Scalar + Neg<Output = T>>(self) -> Vec2<T> {
+        match self {
+            Self::Up => Vec2::new(T::ZERO, -T::ONE),
+            Self::Right => Vec2::new(T::ONE, T::ZERO),
+            Self::Down => Vec2::new(T::ZERO, T::ONE),
+            Self::Left => Vec2::new(-T::ONE, T::ZERO),
+        }
+    }
+}
+
+/// Accepts `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and `^`/`>`/`v`/`<`.
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" | "N" | "^" => Ok(Self::Up),
+            "R" | "E" | ">" => Ok(Self::Right),
+            "D" | "S" | "v" => Ok(Self::Down),
+            "L" | "W" | "<" => Ok(Self::Left),
+            _ => Err(ParseError::at_offset(
+                format!("invalid direction {s:?}"),
+                s,
+                0,
+                s.len(),
+            )),
+        }
+    }
+}
+
+/// One of the eight compass directions, in clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns an eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns an eighth of a turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn to_vec<T: Scalar + Neg<Output = T>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Self::Up => (T::ZERO, -T::ONE),
+            Self::UpRight => (T::ONE, -T::ONE),
+            Self::Right => (T::ONE, T::ZERO),
+            Self::DownRight => (T::ONE, T::ONE),
+            Self::Down => (T::ZERO, T::ONE),
+            Self::DownLeft => (-T::ONE, T::ONE),
+            Self::Left => (-T::ONE, T::ZERO),
+            Self::UpLeft => (-T::ONE, -T::ONE),
+        };
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+/// Implements an axis-aligned box with inclusive bounds over a vector type.
+macro_rules! impl_box {
+    ($(#[$meta:meta])* $name:ident($vector:ident { $($field:ident),* })) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub struct $name<T> {
+            pub min: $vector<T>,
+            pub max: $vector<T>,
+        }
+
+        impl<T: Scalar> $name<T> {
+            /// Creates the smallest box containing both corners.
+            pub fn new(a: $vector<T>, b: $vector<T>) -> Self {
+                Self {
+                    min: a.component_min(b),
+                    max: a.component_max(b),
+                }
+            }
+
+            /// Creates the smallest box containing all of `points`, or `None`
+            /// if there are none.
+            pub fn bounding(points: impl IntoIterator<Item = $vector<T>>) -> Option<Self> {
+                points
+                    .into_iter()
+                    .map(|p| Self::new(p, p))
+                    .reduce(Self::merge)
+            }
+
+            pub fn contains(&self, point: $vector<T>) -> bool {
+                $(self.min.$field <= point.$field && point.$field <= self.max.$field)&&*
+            }
+
+            /// Returns the smallest box containing both boxes.
+            pub fn merge(self, other: Self) -> Self {
+                Self {
+                    min: self.min.component_min(other.min),
+                    max: self.max.component_max(other.max),
+                }
+            }
+
+            /// Returns the overlap of both boxes, if any.
+            pub fn intersection(self, other: Self) -> Option<Self> {
+                let overlap = Self {
+                    min: self.min.component_max(other.min),
+                    max: self.max.component_min(other.max),
+                };
+                ($(overlap.min.$field <= overlap.max.$field)&&*).then_some(overlap)
+            }
+
+            /// The number of points along each axis.
+            pub fn size(&self) -> $vector<T> {
+                self.max - self.min + $vector { $($field: T::ONE),* }
+            }
+        }
+    };
+}
+
+impl_box!(
+    /// An axis-aligned rectangle including both corners.
+    Rect(Vec2 { x, y })
+);
+impl_box!(
+    /// An axis-aligned cuboid including both corners.
+    Cuboid(Vec3 { x, y, z })
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Rect, Vec2, Vec2i, Vec3i};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("3,-4".parse::<Vec2i>().unwrap(), Vec2::new(3, -4));
+        assert_eq!("x=3, y=-4".parse::<Vec2i>().unwrap(), Vec2::new(3, -4));
+        assert_eq!("1,2,3".parse::<Vec3i>().unwrap().product(), 6);
+        assert!("1,2,3".parse::<Vec2i>().is_err());
+        assert!("1,x".parse::<Vec2<u8>>().is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = Vec2i::new(1, 5);
+        let b = Vec2i::new(-2, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((a - b).signum(), Vec2::new(1, 1));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.to_vec::<i32>().rotate_clockwise(),
+                direction.turn_right().to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::bounding([Vec2i::new(4, 1), Vec2i::new(0, 3)]).unwrap();
+        assert_eq!(rect.size(), Vec2::new(5, 3));
+        assert!(rect.contains(Vec2::new(2, 2)));
+        assert!(!rect.contains(Vec2::new(2, 4)));
+        let other = Rect::new(Vec2::new(3, 3), Vec2::new(6, 6));
+        assert_eq!(
+            rect.intersection(other),
+            Some(Rect::new(Vec2::new(3, 3), Vec2::new(4, 3)))
+        );
+        assert_eq!(rect.merge(other).size(), Vec2::new(7, 6));
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{Error, Result};
+
+use crate::{Input, ParseError};
+
+/// Offsets to the four orthogonal neighbours, clockwise from east.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Offsets to all eight neighbours, clockwise from east.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// A rectangular grid of cells stored in row-major order.
+///
+/// `(x, y)` positions index columns and rows, with `(0, 0)` at the top left.
+/// Positions are always `usize`, and [`Grid::offset`] moves from one by a
+/// signed amount.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// Panics if `cells` doesn't hold exactly `width * height` values.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, mapping each character to a cell.
+    ///
+    /// Every line must have the same number of characters.
+    pub fn parse_with<R: BufRead>(reader: R, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = line_number + 1;
+
+            let mut row_width = 0;
+            for (column, c) in line.chars().enumerate() {
+                let cell = f(c).map_err(|e| {
+                    ParseError::new(format!("{e:#}"), line_number, column + 1, 1, &line)
+                })?;
+                cells.push(cell);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::whole_line(
+                        format!("expected a row of {width} cells, found {row_width}"),
+                        line_number,
+                        &line,
+                    )
+                    .into())
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it lies outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[x + y * self.width])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[x + y * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `(x, y)`, wrapping around the edges of the grid in
+    /// either direction so that `-1` is the last column or row, or `None` if
+    /// the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        let (x, y) = self.wrap(x, y)?;
+        Some(&self[(x, y)])
+    }
+
+    pub fn get_wrapping_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        let (x, y) = self.wrap(x, y)?;
+        Some(&mut self[(x, y)])
+    }
+
+    fn wrap(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let x = x.checked_rem_euclid(self.width as isize)?;
+        let y = y.checked_rem_euclid(self.height as isize)?;
+        Some((x as usize, y as usize))
+    }
+
+    /// Returns the position `(dx, dy)` away from `(x, y)`, or `None` if it
+    /// lies outside the grid.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        self.contains(x, y).then_some((x, y))
+    }
+
+    /// Iterates over every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which an empty grid would have
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates over the in-bounds orthogonal neighbours of `(x, y)`.
+    pub fn neighbors_4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBORS_4)
+    }
+
+    /// Iterates over the in-bounds orthogonal and diagonal neighbours of
+    /// `(x, y)`.
+    pub fn neighbors_8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBORS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of bounds"
+        );
+        &self.cells[x + y * self.width]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of bounds"
+        );
+        &mut self.cells[x + y * self.width]
+    }
+}
+
+/// Parses each character with `T`'s `TryFrom<char>`. Use
+/// [`Grid::parse_with`] to supply the mapping directly.
+impl<T> Input for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Into<Error>,
+{
+    fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        Self::parse_with(reader, |c| T::try_from(c).map_err(Into::into))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    fn example() -> Grid<char> {
+        Grid::parse_with("abc\ndef\n".as_bytes(), Ok).unwrap()
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = example();
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = example();
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 1), None);
+        assert_eq!(grid.get_wrapping(5, 2), Some(&'c'));
+        assert_eq!(grid.get_wrapping(-1, -3), Some(&'f'));
+        assert_eq!(grid.offset(1, 1, -1, 0), Some((0, 1)));
+        assert_eq!(grid.offset(0, 1, -1, 0), None);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.neighbors_4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors_8(1, 1).count(), 5);
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = Grid::<char>::parse_with("".as_bytes(), Ok).unwrap();
+        assert_eq!(grid.get(0, 0), None);
+        assert_eq!(grid.get_wrapping(1, 1), None);
+        assert_eq!(grid.get_wrapping(-1, 0), None);
+        assert_eq!(grid.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let error = Grid::parse_with("abc\nde\n".as_bytes(), Ok).err().unwrap();
+        assert_eq!(error.downcast_ref::<crate::ParseError>().unwrap().line(), 2);
+    }
+}
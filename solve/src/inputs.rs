@@ -0,0 +1,168 @@
+//! Finding a day's input files.
+//!
+//! A day's inputs are the `*.input` files in `inputs/day_N/` and in the day's
+//! own directory, relative to the workspace root. Each is identified by its
+//! file name, which is also how the answers file refers to it, so several
+//! people's inputs can sit side by side as `alice.input`, `bob.input` and so
+//! on. When both directories have a file with the same name, the one in
+//! `inputs/` wins.
+//!
+//! A day runs against its `test.input` unless told otherwise, or against
+//! every input found for it if it has no `test.input`, like days whose
+//! examples come as `test_1.input`, `test_2.input` and so on.
+
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::Day;
+
+/// The directory, relative to the workspace root, that holds inputs kept
+/// apart from the days' code.
+pub const INPUTS_DIR: &str = "inputs";
+
+/// The input a day runs against when none is named.
+pub const DEFAULT_INPUT: &str = "test.input";
+
+/// The directories searched for `day`'s inputs, most preferred first.
+fn directories(root: &Path, day: &Day) -> [PathBuf; 2] {
+    [
+        root.join(INPUTS_DIR).join(day.name()),
+        root.join(day.name()),
+    ]
+}
+
+/// Every input found for `day` under `root`, ordered by name.
+pub fn discover_in(root: &Path, day: &Day) -> Result<Vec<PathBuf>> {
+    let mut found = BTreeMap::new();
+    for directory in directories(root, day).iter().rev() {
+        if !directory.is_dir() {
+            continue;
+        }
+        let entries = fs::read_dir(directory)
+            .with_context(|| format!("Failed to list {}", directory.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "input") && path.is_file() {
+                found.insert(path.file_name().unwrap().to_owned(), path);
+            }
+        }
+    }
+    Ok(found.into_values().collect())
+}
+
+/// Every input found for `day` under the current directory, ordered by name.
+pub fn discover(day: &Day) -> Result<Vec<PathBuf>> {
+    discover_in(Path::new(""), day)
+}
+
+pub(crate) fn find_in(root: &Path, day: &Day, name: &str) -> Option<PathBuf> {
+    directories(root, day)
+        .into_iter()
+        .map(|directory| directory.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Finds the input of `day` called `name`, if there is one.
+pub fn find(day: &Day, name: &str) -> Option<PathBuf> {
+    find_in(Path::new(""), day, name)
+}
+
+/// The inputs under `root` to run `day` against when none are named.
+pub fn defaults_in(root: &Path, day: &Day) -> Result<Vec<PathBuf>> {
+    if let Some(path) = find_in(root, day, DEFAULT_INPUT) {
+        return Ok(vec![path]);
+    }
+    let paths = discover_in(root, day)?;
+    if paths.is_empty() {
+        bail!(
+            "no inputs found for day {}, expected them in {}/{}/ or {}/",
+            day.number(),
+            INPUTS_DIR,
+            day.name(),
+            day.name()
+        );
+    }
+    Ok(paths)
+}
+
+/// The inputs to run `day` against when none are named: its
+/// [`DEFAULT_INPUT`], or every input found for it if it has none.
+pub fn defaults(day: &Day) -> Result<Vec<PathBuf>> {
+    defaults_in(Path::new(""), day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{defaults_in, discover_in};
+    use crate::Day;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_discover() {
+        let root = std::env::temp_dir().join(format!("solve-inputs-{}", std::process::id()));
+        for path in [
+            "inputs/day_3/alice.input",
+            "inputs/day_3/test.input",
+            "inputs/day_3/notes.txt",
+            "day_3/test.input",
+            "day_3/big.input",
+            "day_4/other.input",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let day = Day::new(3, |_: &String| 0, |_: &String| 0);
+        let found = discover_in(&root, &day).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let relative = found
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            relative,
+            [
+                Path::new("inputs/day_3/alice.input"),
+                Path::new("day_3/big.input"),
+                Path::new("inputs/day_3/test.input"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_defaults() {
+        let root = std::env::temp_dir().join(format!("solve-defaults-{}", std::process::id()));
+        for path in [
+            "day_5/test.input",
+            "day_5/big.input",
+            "day_6/test_2.input",
+            "day_6/test_1.input",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let day = |number| Day::new(number, |_: &String| 0, |_: &String| 0);
+        let five = defaults_in(&root, &day(5)).unwrap();
+        let six = defaults_in(&root, &day(6)).unwrap();
+        let seven = defaults_in(&root, &day(7));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(five, [root.join("day_5/test.input")]);
+        assert_eq!(
+            six,
+            [
+                root.join("day_6/test_1.input"),
+                root.join("day_6/test_2.input")
+            ]
+        );
+        assert!(seven.is_err());
+    }
+}
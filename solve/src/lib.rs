@@ -1,16 +1,82 @@
-use anyhow::{anyhow, Context, Error, Result};
+pub mod alloc;
+mod answers;
+pub mod bench;
+pub mod buffer;
+pub mod cancel;
+pub mod cube;
+mod error;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod geom;
+pub mod grid;
+pub mod inputs;
+pub mod output;
+pub mod par;
+pub mod params;
+pub mod parser;
+mod registry;
+#[cfg(feature = "repl")]
+mod repl;
+pub mod runner;
+pub mod search;
+pub mod testing;
+pub mod trace;
+pub mod viz;
+
+pub use alloc::Memory;
+pub use answers::Answers;
+pub use buffer::Buffer;
+pub use error::ParseError;
+pub use grid::Grid;
+pub use params::Params;
+pub use registry::{Day, Registry};
+pub use solve_derive::FromStr;
+
+use anyhow::{bail, Context, Error, Result};
+use runner::Failure;
 use std::{
+    any::Any,
     env,
     fmt::{self, Display},
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-    str::{from_utf8, FromStr},
+    io::{self, BufRead},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::{self, FromStr},
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 pub trait Input: Sized {
     fn parse<R: BufRead>(reader: R) -> Result<Self>;
+
+    /// Parses the whole input at once. Input files are read into memory and
+    /// parsed with this, so implementations can borrow from `bytes` instead
+    /// of copying each line out of a reader. Defaults to [`Input::parse`].
+    fn parse_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::parse(bytes)
+    }
+}
+
+/// An input that borrows from the bytes it's parsed from, such as `&str`
+/// lines or `&[u8]` runs of the buffer, rather than copying them out. Days
+/// with one register their parts with [`Day::borrowing`], and the input only
+/// lives as long as the buffer it was parsed from.
+pub trait InputRef<'a>: Sized {
+    fn parse_ref(bytes: &'a [u8]) -> Result<Self>;
+}
+
+/// Names an [`InputRef`] type for every lifetime of the buffer, so that a
+/// day's parts can take its input however long the buffer lives. It's
+/// implemented on the `'static` form of the input:
+///
+/// ```ignore
+/// impl solve::BorrowedInput for Pattern<'static> {
+///     type Input<'a> = Pattern<'a>;
+/// }
+/// ```
+pub trait BorrowedInput: 'static {
+    type Input<'a>: InputRef<'a> + Sync;
 }
 
 impl Input for String {
@@ -19,22 +85,65 @@ impl Input for String {
         reader.read_to_string(&mut result)?;
         Ok(result)
     }
+
+    fn parse_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(str::from_utf8(bytes)
+            .context("Input is not valid UTF-8")?
+            .to_string())
+    }
+}
+
+/// Parses `text`, turning a failure into a [`ParseError`]. Errors that
+/// already know where they happened keep their span and are moved into
+/// place with `relocate`, and any others are placed with `locate`.
+fn parse_located<T: FromStr>(
+    text: &str,
+    relocate: impl FnOnce(ParseError) -> ParseError,
+    locate: impl FnOnce(String) -> ParseError,
+) -> Result<T>
+where
+    T::Err: Into<Error>,
+{
+    T::from_str(text).map_err(|e| {
+        match e.into().downcast::<ParseError>() {
+            Ok(e) => relocate(e),
+            Err(e) => locate(format!("{:#}", e)),
+        }
+        .into()
+    })
+}
+
+fn parse_line<T: FromStr>(line_number: usize, line: &str) -> Result<T>
+where
+    T::Err: Into<Error>,
+{
+    parse_located(
+        line,
+        |e| e.with_line(line_number),
+        |message| ParseError::whole_line(message, line_number, line),
+    )
 }
 
 impl<T: FromStr> Input for Vec<T>
 where
-    T::Err: Display,
+    T::Err: Into<Error>,
 {
     fn parse<R: BufRead>(reader: R) -> Result<Self> {
         reader
             .lines()
             .enumerate()
             .map(|(line_number, line)| {
-                T::from_str(&line.context("Failed to read line")?)
-                    .map_err(|e| anyhow!("Failed to parse line {}: {}", line_number + 1, e))
+                let line = line.context("Failed to read line")?;
+                parse_line(line_number + 1, &line)
             })
             .collect()
     }
+
+    fn parse_bytes(bytes: &[u8]) -> Result<Self> {
+        buffer::lines(bytes)
+            .map(|(line_number, line)| parse_line(line_number, line?))
+            .collect()
+    }
 }
 
 pub struct Unimplemented;
@@ -45,13 +154,42 @@ impl Display for Unimplemented {
     }
 }
 
+/// A value a part can return. Parts that can fail return a [`Result`] of
+/// one of these instead, and the error is reported in place of an answer.
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer! {
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool, char, String, &str,
+    Unimplemented
+}
+
+impl<T: Answer, E: Into<Error>> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
 pub struct CSV<T> {
     pub values: Vec<T>,
 }
 
 impl<T: FromStr> FromStr for CSV<T>
 where
-    T::Err: std::error::Error + Send + Sync + 'static,
+    T::Err: Into<Error>,
 {
     type Err = Error;
 
@@ -62,13 +200,24 @@ where
 
 impl<T: FromStr> Input for CSV<T>
 where
-    T::Err: std::error::Error + Send + Sync + 'static,
+    T::Err: Into<Error>,
 {
     fn parse<R: BufRead>(reader: R) -> Result<Self> {
-        let values = reader
-            .split(b',')
-            .map(|x| Ok(from_utf8(&x?)?.parse()?))
-            .collect::<Result<_, Error>>()?;
+        Self::parse_bytes(String::parse(reader)?.as_bytes())
+    }
+
+    fn parse_bytes(bytes: &[u8]) -> Result<Self> {
+        let source = str::from_utf8(bytes).context("Input is not valid UTF-8")?;
+        let mut offset = 0;
+        let mut values = Vec::new();
+        for value in source.split(',') {
+            values.push(parse_located(
+                value,
+                |e| e.within(source, offset),
+                |message| ParseError::at_offset(message, source, offset, value.len()),
+            )?);
+            offset += value.len() + 1;
+        }
         Ok(Self { values })
     }
 }
@@ -79,81 +228,632 @@ pub struct Grouped<T> {
 
 impl<T: FromStr> Input for Grouped<T>
 where
-    T::Err: std::error::Error + Send + Sync + 'static,
+    T::Err: Into<Error>,
 {
     fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        Self::parse_bytes(String::parse(reader)?.as_bytes())
+    }
+
+    fn parse_bytes(bytes: &[u8]) -> Result<Self> {
         let mut groups = vec![Vec::new()];
-        for line in reader.lines() {
+        for (line_number, line) in buffer::lines(bytes) {
             let line = line?;
             if line.is_empty() {
                 groups.push(Vec::new());
             } else {
-                groups.last_mut().unwrap().push(line.parse()?);
+                groups
+                    .last_mut()
+                    .unwrap()
+                    .push(parse_line(line_number, line)?);
             }
         }
         Ok(Grouped { groups })
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "1" | "one" => Part::One,
+            "2" | "two" => Part::Two,
+            x => bail!("Invalid part \"{}\"", x),
+        })
+    }
+}
+
+/// The outcome of comparing a solution against its expected answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Mismatch { expected: String },
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Mismatch { expected } => write!(f, "mismatch, expected {}", expected),
+        }
+    }
+}
+
+/// How a part finished.
+enum Outcome<T> {
+    Solved(T),
+    Failed(Error),
+    /// The part panicked, with this message.
+    Panicked(String),
+    TimedOut,
+}
+
 pub struct Solution<T> {
-    result: T,
+    outcome: Outcome<T>,
     duration: Duration,
+    /// `None` unless allocations are being counted.
+    memory: Option<Memory>,
+    /// Whether a part that timed out was still running alongside this one.
+    tainted: bool,
+    status: Option<Status>,
 }
 
 impl<T: Display> Display for Solution<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "  Solution: {}", self.result)?;
+        match &self.outcome {
+            Outcome::Solved(result) => writeln!(f, "  Solution: {}", result)?,
+            Outcome::Failed(e) => writeln!(f, "  Error:    {:#}", e)?,
+            Outcome::Panicked(message) => writeln!(f, "  Error:    panicked: {}", message)?,
+            Outcome::TimedOut => writeln!(f, "  Solution: timed out")?,
+        }
         writeln!(f, "  Elapsed:  {} seconds", self.duration.as_secs_f64())?;
+        if let Some(memory) = &self.memory {
+            writeln!(f, "  Memory:   {}", memory)?;
+        }
+        if self.tainted {
+            writeln!(
+                f,
+                "  Note:     measured while a part that timed out was still running"
+            )?;
+        }
+        if let Some(status) = &self.status {
+            writeln!(f, "  Status:   {}", status)?;
+        }
         Ok(())
     }
 }
 
-fn time_solve<F: FnOnce() -> T, T>(f: F) -> Solution<T> {
+impl<T> Solution<T> {
+    /// The answer, or `None` if the part failed or timed out.
+    pub fn result(&self) -> Option<&T> {
+        match &self.outcome {
+            Outcome::Solved(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// The error the part returned, if it failed.
+    pub fn error(&self) -> Option<&Error> {
+        match &self.outcome {
+            Outcome::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The message the part panicked with, if it panicked.
+    pub fn panicked(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Panicked(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    pub fn timed_out(&self) -> bool {
+        matches!(self.outcome, Outcome::TimedOut)
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The heap usage of the part, if allocations were being counted and
+    /// nothing else was allocating at the same time.
+    pub fn memory(&self) -> Option<Memory> {
+        self.memory
+    }
+
+    /// Whether a part that timed out without stopping was still running
+    /// while this one ran, so that its duration is unreliable. See
+    /// [`cancel::abandoned`].
+    pub fn tainted(&self) -> bool {
+        self.tainted
+    }
+
+    /// The result of the last call to `check`, if any.
+    pub fn status(&self) -> Option<&Status> {
+        self.status.as_ref()
+    }
+}
+
+impl<T: Display> Solution<T> {
+    /// Compares the displayed result against an expected answer. A part that
+    /// failed or timed out never matches.
+    pub fn check(&mut self, expected: &str) -> &Status {
+        let matches = self
+            .result()
+            .is_some_and(|result| result.to_string() == expected);
+        self.status.insert(if matches {
+            Status::Pass
+        } else {
+            Status::Mismatch {
+                expected: expected.to_string(),
+            }
+        })
+    }
+}
+
+/// Runs a part against `input`, giving up on it after `timeout` if there is
+/// one.
+fn time_part<I, T>(
+    input: &Arc<I>,
+    timeout: Option<Duration>,
+    f: impl FnOnce(&I) -> Result<T> + Send + 'static,
+) -> Solution<T>
+where
+    I: Send + Sync + 'static,
+    T: Send + 'static,
+{
+    timed(|| match timeout {
+        Some(timeout) => {
+            let input = input.clone();
+            cancel::with_timeout(timeout, move || f(&input))
+        }
+        None => Some(f(input)),
+    })
+}
+
+/// Like [`time_part`], for an input borrowed from `buffer`. A part given a
+/// timeout runs on a thread of its own that may outlive the input, so that
+/// thread parses its own copy from `buffer`. Parsing it isn't counted in the
+/// part's duration.
+fn time_part_ref<B, T>(
+    buffer: &Arc<Buffer>,
+    input: &B::Input<'_>,
+    timeout: Option<Duration>,
+    f: &Arc<impl Fn(&B::Input<'_>) -> Result<T> + Send + Sync + 'static>,
+) -> Solution<T>
+where
+    B: BorrowedInput,
+    T: Send + 'static,
+{
+    let mut reparse = Duration::ZERO;
+    let mut solution = timed(|| match timeout {
+        Some(timeout) => {
+            let (buffer, f) = (buffer.clone(), f.clone());
+            let result = cancel::with_timeout(timeout, move || {
+                let start = Instant::now();
+                let input = B::Input::parse_ref(&buffer);
+                (start.elapsed(), input.and_then(|input| f(&input)))
+            });
+            result.map(|(parse_duration, result)| {
+                reparse = parse_duration;
+                result
+            })
+        }
+        None => Some(f(input)),
+    });
+    solution.duration = solution.duration.saturating_sub(reparse);
+    solution
+}
+
+/// Times a part that `run` runs, which returns `None` if it timed out. A
+/// panic in the part is caught, so that it only costs that part its answer.
+fn timed<T>(run: impl FnOnce() -> Option<Result<T>>) -> Solution<T> {
+    let tainted = cancel::abandoned() > 0;
     let start = Instant::now();
-    let result = f();
-    let duration = Instant::now().duration_since(start);
-    Solution { result, duration }
+    let (result, memory) = alloc::measure(|| panic::catch_unwind(AssertUnwindSafe(run)));
+    let outcome = match result {
+        Ok(Some(Ok(result))) => Outcome::Solved(result),
+        Ok(Some(Err(e))) => Outcome::Failed(e),
+        Ok(None) => Outcome::TimedOut,
+        Err(payload) => Outcome::Panicked(panic_message(&*payload)),
+    };
+    Solution {
+        outcome,
+        duration: start.elapsed(),
+        // The abandoned part's allocations would be counted too
+        memory: memory.filter(|_| !tainted),
+        tainted,
+        status: None,
+    }
+}
+
+/// The message a panic was raised with, if it had one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "no message".to_string()),
+    }
+}
+
+fn parse_input<I: Input>(path: &Path) -> Result<I> {
+    let buffer = Buffer::open(path)?;
+    I::parse_bytes(&buffer).map_err(|e| parse_failed(e, Some(path)))
 }
 
-pub type SolveResult<P1, P2> = Result<(Solution<P1>, Solution<P2>)>;
+/// Describes an error from parsing an input, from the file at `path` if it
+/// was read from one.
+fn parse_failed(mut e: Error, path: Option<&Path>) -> Error {
+    if let (Some(parse_error), Some(path)) = (e.downcast_mut::<ParseError>(), path) {
+        parse_error.set_path(path);
+    }
+    e.context("Failed to parse input")
+}
+
+/// Where to read a puzzle input from.
+#[derive(Clone, Copy)]
+pub(crate) enum Source<'a> {
+    Path(&'a Path),
+    Text(&'a str),
+}
+
+impl<'a> Source<'a> {
+    fn parse<I: Input>(self) -> Result<I> {
+        match self {
+            Source::Path(path) => parse_input(path),
+            Source::Text(text) => {
+                I::parse_bytes(text.as_bytes()).map_err(|e| parse_failed(e, None))
+            }
+        }
+    }
+
+    /// Reads the whole input into a buffer for an [`InputRef`] to borrow from.
+    fn buffer(self) -> Result<Buffer> {
+        match self {
+            Source::Path(path) => Buffer::open(path),
+            Source::Text(text) => Ok(Buffer::Owned(text.as_bytes().to_vec())),
+        }
+    }
 
+    /// The file the input is read from, if it's read from one.
+    fn path(self) -> Option<&'a Path> {
+        match self {
+            Source::Path(path) => Some(path),
+            Source::Text(_) => None,
+        }
+    }
+}
+
+/// The timings and solutions for a single run of both parts.
+pub struct Report<P1, P2> {
+    pub parse_duration: Duration,
+    pub parse_memory: Option<Memory>,
+    pub part_one: Solution<P1>,
+    pub part_two: Solution<P2>,
+}
+
+impl<P1, P2> Report<P1, P2> {
+    /// The time spent parsing the input and solving both parts.
+    pub fn total_duration(&self) -> Duration {
+        self.parse_duration + self.part_one.duration + self.part_two.duration
+    }
+}
+
+pub type SolveResult<P1, P2> = Result<Report<P1, P2>>;
+
+/// Parses the input at `path` and solves both parts, giving each part up to
+/// `timeout` to finish.
 pub fn solve<I, P1, P2>(
     path: &Path,
-    solve_part_one: impl FnOnce(&I) -> P1,
-    solve_part_two: impl FnOnce(&I) -> P2,
+    timeout: Option<Duration>,
+    solve_part_one: impl FnOnce(&I) -> P1 + Send + 'static,
+    solve_part_two: impl FnOnce(&I) -> P2 + Send + 'static,
 ) -> SolveResult<P1, P2>
 where
-    I: Input,
-    P1: Display,
-    P2: Display,
+    I: Input + Send + Sync + 'static,
+    P1: Display + Send + 'static,
+    P2: Display + Send + 'static,
 {
-    let input_file = BufReader::new(File::open(path).context("Failed to open input file")?);
-    let input = Input::parse(input_file).context("Failed to parse input")?;
+    solve_source(
+        Source::Path(path),
+        timeout,
+        move |input| Ok(solve_part_one(input)),
+        move |input| Ok(solve_part_two(input)),
+    )
+}
 
-    Ok((
-        time_solve(|| solve_part_one(&input)),
-        time_solve(|| solve_part_two(&input)),
-    ))
+/// Like [`solve`], but the parts may fail, in which case their errors are
+/// reported in place of their answers.
+fn solve_source<I, P1, P2>(
+    source: Source,
+    timeout: Option<Duration>,
+    solve_part_one: impl FnOnce(&I) -> Result<P1> + Send + 'static,
+    solve_part_two: impl FnOnce(&I) -> Result<P2> + Send + 'static,
+) -> SolveResult<P1, P2>
+where
+    I: Input + Send + Sync + 'static,
+    P1: Display + Send + 'static,
+    P2: Display + Send + 'static,
+{
+    let tainted = cancel::abandoned() > 0;
+    let start = Instant::now();
+    let (input, parse_memory) = alloc::measure(|| source.parse::<I>());
+    let parse_memory = parse_memory.filter(|_| !tainted);
+    let input = Arc::new(input?);
+    let parse_duration = start.elapsed();
+
+    Ok(Report {
+        parse_duration,
+        parse_memory,
+        part_one: time_part(&input, timeout, solve_part_one),
+        part_two: time_part(&input, timeout, solve_part_two),
+    })
 }
 
-pub fn main<I, P1, P2>(solve_part_one: impl FnOnce(&I) -> P1, solve_part_two: impl FnOnce(&I) -> P2)
+/// Like [`solve_source`], for a day whose input borrows from the buffer it's
+/// read into. The buffer is kept alive until both parts have run.
+fn solve_source_ref<B, P1, P2>(
+    source: Source,
+    timeout: Option<Duration>,
+    solve_part_one: &Arc<impl Fn(&B::Input<'_>) -> Result<P1> + Send + Sync + 'static>,
+    solve_part_two: &Arc<impl Fn(&B::Input<'_>) -> Result<P2> + Send + Sync + 'static>,
+) -> SolveResult<P1, P2>
 where
-    I: Input,
-    P1: Display,
-    P2: Display,
+    B: BorrowedInput,
+    P1: Display + Send + 'static,
+    P2: Display + Send + 'static,
 {
-    let bin_path = env::args().nth(0).expect("missing binary path");
-    let path = env::args().nth(1).unwrap_or_else(|| {
-        let path = Path::new(&bin_path);
-        let day = path.file_stem().unwrap().to_str().unwrap();
-        format!("{day}/test.input")
-    });
-    println!("opening {path}");
-    let (part_one, part_two) =
-        solve(path.as_ref(), solve_part_one, solve_part_two).expect("failed to solve problem");
+    let tainted = cancel::abandoned() > 0;
+    let start = Instant::now();
+    let measurement = alloc::Measurement::start();
+    let buffer = Arc::new(source.buffer()?);
+    let input = B::Input::parse_ref(&buffer).map_err(|e| parse_failed(e, source.path()))?;
+    let parse_memory = measurement.finish().filter(|_| !tainted);
+    let parse_duration = start.elapsed();
 
+    Ok(Report {
+        parse_duration,
+        parse_memory,
+        part_one: time_part_ref::<B, _>(&buffer, &input, timeout, solve_part_one),
+        part_two: time_part_ref::<B, _>(&buffer, &input, timeout, solve_part_two),
+    })
+}
+
+/// The name an input is identified by in the answers file.
+fn input_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Reads the paths of the inputs to solve and the parameter overrides to solve
+/// them with from the command line, and starts tracing or visualising if asked
+/// to. No paths are returned if none were given.
+fn parse_args(day: &Day) -> Result<(Vec<PathBuf>, Vec<params::Assignment>)> {
+    let mut paths = Vec::new();
+    let mut overrides = Vec::new();
+    let mut directives = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--param" {
+            let assignment = args.next().context("expected `name=value` after --param")?;
+            overrides.push(params::parse_assignment(&assignment)?);
+        } else if arg == "--visualize" {
+            viz::init(viz::Playback::default());
+        } else if arg == "--trace" {
+            let directive = args.next().context("expected a target after --trace")?;
+            directives.push(directive.parse()?);
+        } else if Path::new(&arg).is_file() {
+            paths.push(PathBuf::from(arg));
+        } else {
+            // Inputs can also be given by name, as the runner's --input takes them
+            paths.push(inputs::find(day, &arg).unwrap_or_else(|| PathBuf::from(arg)));
+        }
+    }
+    for (name, _) in overrides.iter() {
+        if !day.param_specs().iter().any(|spec| spec.name() == name) {
+            bail!("day {} has no parameter named `{name}`", day.number());
+        }
+    }
+    day.params(&overrides)?;
+    if !directives.is_empty() {
+        trace::init(directives, io::stderr());
+    }
+    Ok((paths, overrides))
+}
+
+/// Solves and prints one input, returning how it failed if it did.
+fn solve_and_print(
+    day: &Day,
+    path: &Path,
+    overrides: &[(String, String)],
+    answers: &Answers,
+) -> Option<Failure> {
+    println!("opening {}", path.display());
+    let input = input_name(path);
+    let (overrides, checked) = answers.overrides(day, &input, overrides);
+    let result = day
+        .params(&overrides)
+        .and_then(|params| day.solve(path, &params, None));
+    let mut report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return Some(Failure::of_input(&e));
+        }
+    };
+
+    if checked {
+        answers.check(day.number(), &input, Part::One, &mut report.part_one);
+        answers.check(day.number(), &input, Part::Two, &mut report.part_two);
+    }
+
+    println!("Parse:");
+    println!(
+        "  Elapsed:  {} seconds",
+        report.parse_duration.as_secs_f64()
+    );
+    if let Some(memory) = report.parse_memory {
+        println!("  Memory:   {}", memory);
+    }
+    println!();
     println!("Part one:");
-    println!("{}", part_one);
+    println!("{}", report.part_one);
     println!("Part two:");
-    println!("{}", part_two);
+    println!("{}", report.part_two);
+    println!("Total:");
+    println!(
+        "  Elapsed:  {} seconds",
+        report.total_duration().as_secs_f64()
+    );
+
+    [&report.part_one, &report.part_two]
+        .into_iter()
+        .filter_map(Failure::of_part)
+        .min()
+}
+
+/// Solves each input given on the command line, or the day's default inputs
+/// if none were, as described in [`inputs`].
+pub fn main(day: Day) -> ExitCode {
+    let (paths, overrides) = match parse_args(&day) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let paths = match paths {
+        paths if !paths.is_empty() => paths,
+        _ => match inputs::defaults(&day) {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                return Failure::MissingInput.exit_code();
+            }
+        },
+    };
+    let answers = match Answers::load_default() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        failures.extend(solve_and_print(&day, path, &overrides, &answers));
+    }
+    failures
+        .into_iter()
+        .min()
+        .map_or(ExitCode::SUCCESS, Failure::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        solve_source, solve_source_ref, BorrowedInput, Grouped, Input, InputRef, ParseError,
+        Source, CSV,
+    };
+    use std::{str::FromStr, sync::Arc, time::Duration};
+
+    /// Fails on an `x`, pointing at it.
+    #[derive(Debug)]
+    struct Digits;
+
+    impl FromStr for Digits {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, ParseError> {
+            match s.find('x') {
+                Some(i) => Err(ParseError::new("unexpected x", 1, i + 1, 1, s)),
+                None => Ok(Digits),
+            }
+        }
+    }
+
+    fn parse_error<I: Input>(source: &str) -> ParseError {
+        let Err(e) = I::parse_bytes(source.as_bytes()) else {
+            panic!("expected {source:?} not to parse");
+        };
+        e.downcast().unwrap()
+    }
+
+    #[test]
+    fn test_grouped_keeps_span() {
+        let error = parse_error::<Grouped<Digits>>("12\n\n34\n5x6\n");
+        assert_eq!((error.line(), error.column()), (4, 2));
+        assert_eq!(error.snippet(), "5x6");
+    }
+
+    #[test]
+    fn test_csv_keeps_span() {
+        let error = parse_error::<CSV<Digits>>("12,34x");
+        assert_eq!((error.line(), error.column()), (1, 6));
+        assert_eq!(error.snippet(), "12,34x");
+    }
+
+    struct Words<'a>(Vec<&'a str>);
+
+    impl<'a> InputRef<'a> for Words<'a> {
+        fn parse_ref(bytes: &'a [u8]) -> anyhow::Result<Self> {
+            Ok(Self(
+                std::str::from_utf8(bytes)?.split_whitespace().collect(),
+            ))
+        }
+    }
+
+    impl BorrowedInput for Words<'static> {
+        type Input<'a> = Words<'a>;
+    }
+
+    #[test]
+    fn test_part_panics() {
+        let report = solve_source(
+            Source::Text("1\n"),
+            None,
+            |input: &String| Ok(input.len()),
+            |_: &String| -> anyhow::Result<usize> { panic!("part two broke") },
+        )
+        .unwrap();
+        assert_eq!(report.part_one.result(), Some(&2));
+        assert_eq!(report.part_two.panicked(), Some("part two broke"));
+    }
+
+    #[test]
+    fn test_borrowed_input() {
+        let count = Arc::new(|words: &Words| Ok(words.0.len()));
+        let join = Arc::new(|words: &Words| Ok(words.0.join("-")));
+        // A part given a timeout parses its own copy on another thread
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let report =
+                solve_source_ref::<Words, _, _>(Source::Text("a b\nc\n"), timeout, &count, &join)
+                    .unwrap();
+            assert_eq!(report.part_one.result(), Some(&3));
+            assert_eq!(report.part_two.result().unwrap(), "a-b-c");
+        }
+    }
 }
@@ -0,0 +1,234 @@
+//! Machine-readable runner output, one record per day and part.
+
+use crate::{Memory, Part, Solution, Status};
+use anyhow::{bail, Error, Result};
+use std::{fmt::Write as _, str::FromStr, time::Duration};
+
+/// How the runner prints its results.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// A table for people to read.
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            "csv" => Format::Csv,
+            x => bail!("unknown format `{x}`, expected json, csv or text"),
+        })
+    }
+}
+
+/// The result of running one part of a day.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    /// `None` if the part didn't produce an answer.
+    pub answer: Option<String>,
+    pub duration: Duration,
+    /// One of `pass`, `mismatch`, `timeout`, `error` or `panicked`, or empty
+    /// if there was no answer to check against.
+    pub status: String,
+    pub expected: Option<String>,
+    pub memory: Option<Memory>,
+    /// Whether a part that timed out was still running at the same time, so
+    /// that the duration can't be trusted.
+    pub tainted: bool,
+}
+
+impl Record {
+    pub fn new(day: u32, part: Part, input: &str, solution: &Solution<String>) -> Self {
+        let (status, expected) = match solution.status() {
+            _ if solution.timed_out() => ("timeout", None),
+            _ if solution.error().is_some() => ("error", None),
+            _ if solution.panicked().is_some() => ("panicked", None),
+            Some(Status::Pass) => ("pass", None),
+            Some(Status::Mismatch { expected }) => ("mismatch", Some(expected.clone())),
+            None => ("", None),
+        };
+        Self {
+            day,
+            part,
+            input: input.to_string(),
+            answer: solution.result().cloned(),
+            duration: solution.duration(),
+            status: status.to_string(),
+            expected,
+            memory: solution.memory(),
+            tainted: solution.tainted(),
+        }
+    }
+
+    /// A record for a part that couldn't run because its day failed.
+    pub fn failed(day: u32, part: Part, input: &str, status: &str) -> Self {
+        Self {
+            day,
+            part,
+            input: input.to_string(),
+            answer: None,
+            duration: Duration::ZERO,
+            status: status.to_string(),
+            expected: None,
+            memory: None,
+            tainted: false,
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, Field<'_>); 11] {
+        let memory = |f: fn(&Memory) -> u64| self.memory.as_ref().map(f);
+        [
+            ("day", Field::Number(Some(self.day.into()))),
+            ("part", Field::Number(Some((self.part as u64) + 1))),
+            ("input", Field::Text(Some(&self.input))),
+            ("answer", Field::Text(self.answer.as_deref())),
+            ("seconds", Field::Seconds(self.duration)),
+            ("status", Field::Text(Some(&self.status))),
+            ("expected", Field::Text(self.expected.as_deref())),
+            ("peak_bytes", Field::Number(memory(|m| m.peak))),
+            ("allocated_bytes", Field::Number(memory(|m| m.allocated))),
+            ("allocations", Field::Number(memory(|m| m.allocations))),
+            ("tainted", Field::Flag(self.tainted)),
+        ]
+    }
+}
+
+enum Field<'a> {
+    Number(Option<u64>),
+    Flag(bool),
+    Seconds(Duration),
+    Text(Option<&'a str>),
+}
+
+/// Writes records as a JSON array, one record per line so that changes show
+/// up clearly in diffs.
+pub fn to_json(records: &[Record]) -> String {
+    let mut output = String::from("[\n");
+    for (i, record) in records.iter().enumerate() {
+        let fields = record
+            .fields()
+            .into_iter()
+            .map(|(name, field)| {
+                let value = match field {
+                    Field::Number(Some(n)) => n.to_string(),
+                    Field::Flag(flag) => flag.to_string(),
+                    Field::Seconds(duration) => duration.as_secs_f64().to_string(),
+                    Field::Text(Some(text)) => json_string(text),
+                    Field::Number(None) | Field::Text(None) => "null".to_string(),
+                };
+                format!("\"{name}\": {value}")
+            })
+            .collect::<Vec<_>>();
+        let separator = if i + 1 < records.len() { "," } else { "" };
+        writeln!(output, "  {{{}}}{separator}", fields.join(", ")).unwrap();
+    }
+    output.push(']');
+    output
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Writes records as CSV with a header row. Missing values are left empty.
+pub fn to_csv(records: &[Record]) -> String {
+    let mut output = String::new();
+    let Some(first) = records.first() else {
+        return output;
+    };
+    let header = first.fields().map(|(name, _)| name);
+    writeln!(output, "{}", header.join(",")).unwrap();
+
+    for record in records {
+        let row = record.fields().map(|(_, field)| match field {
+            Field::Number(n) => n.map(|n| n.to_string()).unwrap_or_default(),
+            Field::Flag(flag) => flag.to_string(),
+            Field::Seconds(duration) => duration.as_secs_f64().to_string(),
+            Field::Text(text) => csv_field(text.unwrap_or_default()),
+        });
+        writeln!(output, "{}", row.join(",")).unwrap();
+    }
+    output
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_csv, to_json, Record};
+    use crate::{Memory, Part};
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 10,
+                part: Part::Two,
+                input: "day_10/test.input".to_string(),
+                answer: Some("#.\n\"a, b\"".to_string()),
+                duration: Duration::from_millis(1500),
+                status: "mismatch".to_string(),
+                expected: Some("x".to_string()),
+                memory: Some(Memory {
+                    allocated: 30,
+                    allocations: 2,
+                    peak: 20,
+                }),
+                tainted: true,
+            },
+            Record::failed(11, Part::One, "day_11/test.input", "error"),
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            to_json(&records()),
+            "[\n  {\"day\": 10, \"part\": 2, \"input\": \"day_10/test.input\", \
+             \"answer\": \"#.\\n\\\"a, b\\\"\", \"seconds\": 1.5, \"status\": \"mismatch\", \
+             \"expected\": \"x\", \"peak_bytes\": 20, \"allocated_bytes\": 30, \
+             \"allocations\": 2, \"tainted\": true},\n  \
+             {\"day\": 11, \"part\": 1, \"input\": \"day_11/test.input\", \"answer\": null, \
+             \"seconds\": 0, \"status\": \"error\", \"expected\": null, \"peak_bytes\": null, \
+             \"allocated_bytes\": null, \"allocations\": null, \"tainted\": false}\n]"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            to_csv(&records()),
+            "day,part,input,answer,seconds,status,expected,peak_bytes,allocated_bytes,allocations,tainted\n\
+             10,2,day_10/test.input,\"#.\n\"\"a, b\"\"\",1.5,mismatch,x,20,30,2,true\n\
+             11,1,day_11/test.input,,0,error,,,,,false\n"
+        );
+    }
+}
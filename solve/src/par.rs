@@ -0,0 +1,320 @@
+//! Running independent pieces of work on several threads.
+//!
+//! Results always come back in the same order as the inputs, however the work
+//! was spread across threads. Work done under a cancelled token returns
+//! [`Cancelled`] instead, since some items may not have been run.
+
+use crate::cancel::{self, Cancelled};
+use std::{
+    num::NonZeroUsize,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread::{self, ScopedJoinHandle},
+    time::{Duration, Instant},
+};
+
+/// A value along with how long it took to compute.
+#[derive(Clone, Copy, Debug)]
+pub struct Timed<T> {
+    pub value: T,
+    pub duration: Duration,
+}
+
+/// A fixed number of worker threads to share work between.
+///
+/// Threads are started for each call and finish before it returns, so the
+/// work can borrow from the caller.
+#[derive(Clone, Copy, Debug)]
+pub struct Pool {
+    threads: NonZeroUsize,
+}
+
+impl Default for Pool {
+    /// A pool with one thread per available CPU.
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+impl Pool {
+    pub fn new(threads: NonZeroUsize) -> Self {
+        Self { threads }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads.get()
+    }
+
+    /// Calls `f` on each item, returning the results in order.
+    pub fn map<T, U>(
+        &self,
+        items: impl IntoIterator<Item = T, IntoIter: Send>,
+        f: impl Fn(T) -> U + Sync,
+    ) -> Result<Vec<U>, Cancelled>
+    where
+        T: Send,
+        U: Send,
+    {
+        let timed = self.map_timed(items, f)?;
+        Ok(timed.into_iter().map(|timed| timed.value).collect())
+    }
+
+    /// Calls `f` on each item, returning the results in order along with how
+    /// long each call took.
+    ///
+    /// If any call panics, the panic is resumed on the calling thread once the
+    /// other threads have finished. Workers share the caller's cancellation
+    /// token, and stop taking new items once it's cancelled, in which case
+    /// [`Cancelled`] is returned.
+    pub fn map_timed<T, U>(
+        &self,
+        items: impl IntoIterator<Item = T, IntoIter: Send>,
+        f: impl Fn(T) -> U + Sync,
+    ) -> Result<Vec<Timed<U>>, Cancelled>
+    where
+        T: Send,
+        U: Send,
+    {
+        let run = |item| {
+            let start = Instant::now();
+            let value = f(item);
+            Timed {
+                value,
+                duration: start.elapsed(),
+            }
+        };
+
+        if self.threads() == 1 {
+            let mut results = Vec::new();
+            for item in items {
+                results.push(run(item));
+                if cancel::is_cancelled() {
+                    return Err(Cancelled);
+                }
+            }
+            return Ok(results);
+        }
+
+        // Workers take the next item whenever they finish one, so uneven
+        // pieces of work still spread out evenly
+        let items = Mutex::new(items.into_iter().enumerate());
+        let next = || items.lock().unwrap().next();
+        let token = cancel::current();
+        let mut results = thread::scope(|scope| {
+            let workers = (0..self.threads())
+                .map(|_| {
+                    scope.spawn(|| {
+                        cancel::with(token.clone(), || {
+                            let mut done = Vec::new();
+                            while let Some((i, item)) = next() {
+                                done.push((i, run(item)));
+                                if cancel::is_cancelled() {
+                                    break;
+                                }
+                            }
+                            done
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            join(workers).into_iter().flatten().collect::<Vec<_>>()
+        });
+
+        if cancel::is_cancelled() {
+            return Err(Cancelled);
+        }
+        results.sort_by_key(|(i, _)| *i);
+        Ok(results.into_iter().map(|(_, timed)| timed).collect())
+    }
+
+    /// Calls `f` on each item until one returns `Some`, and returns the
+    /// result of the earliest item that did, as a sequential search would.
+    ///
+    /// No items after one with a result are started, though calls already
+    /// running on other threads are left to finish, so each call should be a
+    /// modest piece of work. Panics and cancellation are handled as in
+    /// [`Pool::map_timed`].
+    pub fn find_map_first<T, U>(
+        &self,
+        items: impl IntoIterator<Item = T, IntoIter: Send>,
+        f: impl Fn(T) -> Option<U> + Sync,
+    ) -> Result<Option<U>, Cancelled>
+    where
+        T: Send,
+        U: Send,
+    {
+        if self.threads() == 1 {
+            for item in items {
+                let found = f(item);
+                if cancel::is_cancelled() {
+                    return Err(Cancelled);
+                }
+                if found.is_some() {
+                    return Ok(found);
+                }
+            }
+            return Ok(None);
+        }
+
+        let items = Mutex::new(items.into_iter().enumerate());
+        // The earliest item known to have a result
+        let found = AtomicUsize::new(usize::MAX);
+        let next = || {
+            let (i, item) = items.lock().unwrap().next()?;
+            // Items are handed out in order, so none left can come first
+            (i < found.load(Ordering::Relaxed)).then_some((i, item))
+        };
+        let token = cancel::current();
+        let results = thread::scope(|scope| {
+            let workers = (0..self.threads())
+                .map(|_| {
+                    scope.spawn(|| {
+                        cancel::with(token.clone(), || {
+                            while let Some((i, item)) = next() {
+                                if let Some(value) = f(item) {
+                                    found.fetch_min(i, Ordering::Relaxed);
+                                    return Some((i, value));
+                                }
+                                if cancel::is_cancelled() {
+                                    break;
+                                }
+                            }
+                            None
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            join(workers)
+        });
+
+        if cancel::is_cancelled() {
+            return Err(Cancelled);
+        }
+        Ok(results
+            .into_iter()
+            .flatten()
+            .min_by_key(|(i, _)| *i)
+            .map(|(_, value)| value))
+    }
+}
+
+/// Waits for every worker, then resumes the panic of any that panicked.
+fn join<R>(workers: Vec<ScopedJoinHandle<'_, R>>) -> Vec<R> {
+    let mut results = Vec::new();
+    let mut panicked = None;
+    for worker in workers {
+        match worker.join() {
+            Ok(result) => results.push(result),
+            Err(payload) => panicked = Some(payload),
+        }
+    }
+    if let Some(payload) = panicked {
+        panic::resume_unwind(payload);
+    }
+    results
+}
+
+/// Calls `f` on each item using one thread per CPU, returning the results in
+/// order.
+pub fn map<T, U>(
+    items: impl IntoIterator<Item = T, IntoIter: Send>,
+    f: impl Fn(T) -> U + Sync,
+) -> Result<Vec<U>, Cancelled>
+where
+    T: Send,
+    U: Send,
+{
+    Pool::default().map(items, f)
+}
+
+/// Like [`map`], but also returns how long each call took.
+pub fn map_timed<T, U>(
+    items: impl IntoIterator<Item = T, IntoIter: Send>,
+    f: impl Fn(T) -> U + Sync,
+) -> Result<Vec<Timed<U>>, Cancelled>
+where
+    T: Send,
+    U: Send,
+{
+    Pool::default().map_timed(items, f)
+}
+
+/// Like [`Pool::find_map_first`], using one thread per CPU.
+pub fn find_map_first<T, U>(
+    items: impl IntoIterator<Item = T, IntoIter: Send>,
+    f: impl Fn(T) -> Option<U> + Sync,
+) -> Result<Option<U>, Cancelled>
+where
+    T: Send,
+    U: Send,
+{
+    Pool::default().find_map_first(items, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::Pool;
+    use crate::cancel::{self, CancellationToken, Cancelled};
+
+    #[test]
+    fn test_map_order() {
+        for threads in [1, 3, 8] {
+            let pool = Pool::new(NonZeroUsize::new(threads).unwrap());
+            let squares = pool.map(0..100u64, |i| {
+                // Make later items finish first
+                std::thread::sleep(std::time::Duration::from_micros(100 - i));
+                i * i
+            });
+            assert_eq!(squares, Ok((0..100).map(|i| i * i).collect::<Vec<_>>()));
+        }
+    }
+
+    #[test]
+    fn test_find_map_first() {
+        for threads in [1, 3, 8] {
+            let pool = Pool::new(NonZeroUsize::new(threads).unwrap());
+            let calls = AtomicUsize::new(0);
+            let found = pool.find_map_first(0..10_000u64, |i| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                // Make later items finish first
+                std::thread::sleep(std::time::Duration::from_micros(200 - i % 200));
+                (i > 100 && i % 7 == 6).then_some(i)
+            });
+            assert_eq!(found, Ok(Some(104)));
+            assert!(calls.into_inner() < 200);
+        }
+    }
+
+    #[test]
+    fn test_cancelled() {
+        for threads in [1, 3] {
+            let pool = Pool::new(NonZeroUsize::new(threads).unwrap());
+            let token = CancellationToken::new();
+            let result = cancel::with(Some(token.clone()), || {
+                pool.map(0..100, |i| {
+                    if i == 10 {
+                        token.cancel();
+                    }
+                    i
+                })
+            });
+            assert_eq!(result, Err(Cancelled));
+
+            let result = cancel::with(Some(token.clone()), || {
+                pool.find_map_first(0..100, |i| (i == 50).then_some(i))
+            });
+            assert_eq!(result, Err(Cancelled));
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Named settings that days read instead of hardcoding, like the row day 15
+//! counts positions in or how many minutes day 16 has.
+//!
+//! A day declares each parameter with a default using [`Day::param`], and
+//! its part functions receive the values to use as [`Params`]. Values given
+//! on the command line as `--param name=value` are parsed as the type of the
+//! default before anything runs, so reading them can't fail on bad input.
+//!
+//! Inputs that need their own values, like an example covering a smaller
+//! area than the real puzzle, list them next to their answers in
+//! `answers.txt` (see [`Answers`]). Those replace the defaults whenever the
+//! input is solved, by the runner or by the day's `answers` test, and
+//! `--param` still goes on top.
+//!
+//! [`Answers`]: crate::Answers
+//! [`Day::param`]: crate::Day::param
+
+use anyhow::{bail, Context, Error, Result};
+use std::{
+    any::{self, Any},
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::Arc,
+};
+
+type Value = Arc<dyn Any + Send + Sync>;
+
+type Parse = dyn Fn(&str) -> Result<Value> + Send + Sync;
+
+/// A parameter declared by a day.
+pub struct Spec {
+    name: &'static str,
+    default: String,
+    default_value: Value,
+    type_name: &'static str,
+    parse: Box<Parse>,
+}
+
+impl Spec {
+    pub(crate) fn new<T>(name: &'static str, default: T) -> Self
+    where
+        T: FromStr + Display + Send + Sync + 'static,
+        T::Err: Into<Error>,
+    {
+        Self {
+            name,
+            default: default.to_string(),
+            default_value: Arc::new(default),
+            type_name: any::type_name::<T>(),
+            parse: Box::new(|value| {
+                let value = value.parse::<T>().map_err(Into::into)?;
+                Ok(Arc::new(value) as Value)
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn default(&self) -> &str {
+        &self.default
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// A parameter's name and the value it's set to, before it's parsed.
+pub type Assignment = (String, String);
+
+/// Parses a `name=value` command line argument.
+pub fn parse_assignment(s: &str) -> Result<Assignment> {
+    let (name, value) = s
+        .split_once('=')
+        .with_context(|| format!("expected `name=value`, found `{s}`"))?;
+    if name.is_empty() {
+        bail!("expected a parameter name before `=` in `{s}`");
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// The values of a day's parameters for one run, already parsed as the
+/// types the day declared them with.
+#[derive(Clone, Default)]
+pub struct Params {
+    values: HashMap<&'static str, Value>,
+}
+
+impl Params {
+    /// Starts from the defaults in `specs`, replacing any that are given in
+    /// `overrides`. Overrides for parameters not in `specs` are ignored.
+    pub(crate) fn resolve(specs: &[Spec], overrides: &[(String, String)]) -> Result<Self> {
+        let mut values = HashMap::new();
+        for spec in specs {
+            let value = match overrides.iter().rev().find(|(name, _)| name == spec.name) {
+                Some((_, value)) => (spec.parse)(value).with_context(|| {
+                    format!(
+                        "invalid value `{value}` for `{}`, expected {}",
+                        spec.name, spec.type_name
+                    )
+                })?,
+                None => spec.default_value.clone(),
+            };
+            values.insert(spec.name, value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Returns the value of the parameter called `name`.
+    ///
+    /// Panics if the day didn't declare it, or declared it with a different
+    /// type.
+    pub fn get<T: Clone + 'static>(&self, name: &str) -> T {
+        let value = self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("parameter `{name}` was never declared"));
+        value
+            .downcast_ref::<T>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "parameter `{name}` was declared with a different type than {}",
+                    any::type_name::<T>()
+                )
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_assignment, Params, Spec};
+
+    #[test]
+    fn test_resolve() {
+        let specs = [
+            Spec::new("row", 2_000_000i32),
+            Spec::new("name", "x".to_string()),
+        ];
+
+        let defaults = Params::resolve(&specs, &[]).unwrap();
+        assert_eq!(defaults.get::<i32>("row"), 2_000_000);
+        assert_eq!(defaults.get::<String>("name"), "x");
+
+        let overrides = [
+            parse_assignment("row=10").unwrap(),
+            parse_assignment("other=1").unwrap(),
+        ];
+        assert_eq!(
+            Params::resolve(&specs, &overrides)
+                .unwrap()
+                .get::<i32>("row"),
+            10
+        );
+
+        let invalid = [parse_assignment("row=ten").unwrap()];
+        let error = Params::resolve(&specs, &invalid).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "invalid value `ten` for `row`, expected i32"
+        );
+        assert!(parse_assignment("row").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "declared with a different type than i64")]
+    fn test_get_wrong_type() {
+        let params = Params::resolve(&[Spec::new("row", 10i32)], &[]).unwrap();
+        params.get::<i64>("row");
+    }
+}
@@ -0,0 +1,557 @@
+//! Small parser combinators for puzzle input formats.
+//!
+//! A [`Parser`] consumes a prefix of its input and returns the parsed value
+//! along with the rest of the input. Functions with the signature
+//! `fn(&str) -> PResult<T>` are parsers, string literals parse themselves, and
+//! tuples of parsers parse each element in sequence:
+//!
+//! ```
+//! use solve::parser::{self, integer, Parser};
+//!
+//! let point = ("x=", integer::<i32>(), ", y=", integer::<i32>()).map(|(_, x, _, y)| (x, y));
+//! assert_eq!(parser::parse(point, "x=-2, y=15").unwrap(), (-2, 15));
+//! ```
+//!
+//! Failures are turned into [`ParseError`]s that point at where parsing
+//! stopped.
+
+use crate::{Input, ParseError};
+use anyhow::Result;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    io::BufRead,
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// Why a parser failed, and where in the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Failure<'a> {
+    at: &'a str,
+    len: usize,
+    kind: FailureKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum FailureKind {
+    Expected(Vec<Cow<'static, str>>),
+    Invalid(String),
+}
+
+impl<'a> Failure<'a> {
+    /// A failure at the start of `at` because it didn't match `expected`.
+    pub fn expected(at: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            at,
+            len: at.chars().next().map_or(0, char::len_utf8),
+            kind: FailureKind::Expected(vec![expected.into()]),
+        }
+    }
+
+    /// A failure for the first `len` bytes of `at`, which matched but weren't
+    /// valid.
+    pub fn invalid(at: &'a str, len: usize, message: impl Display) -> Self {
+        Self {
+            at,
+            len,
+            kind: FailureKind::Invalid(message.to_string()),
+        }
+    }
+
+    /// Keeps whichever failure got further into the input, combining the
+    /// expectations of failures at the same position.
+    fn merge(self, other: Self) -> Self {
+        match self.at.len().cmp(&other.at.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => match (self.kind, other.kind) {
+                (FailureKind::Expected(mut a), FailureKind::Expected(b)) => {
+                    a.extend(b);
+                    Self {
+                        kind: FailureKind::Expected(a),
+                        ..self
+                    }
+                }
+                (kind, _) => Self { kind, ..self },
+            },
+        }
+    }
+
+    /// Converts this failure into an error against the full `source` that was
+    /// being parsed.
+    pub fn into_error(self, source: &str) -> ParseError {
+        let offset = source.len() - self.at.len();
+        ParseError::at_offset(&self, source, offset, self.len)
+    }
+}
+
+impl Display for Failure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            FailureKind::Expected(expected) => {
+                write!(f, "expected ")?;
+                for (i, e) in expected.iter().enumerate() {
+                    if i == 0 {
+                        write!(f, "{e}")?;
+                    } else if i == expected.len() - 1 {
+                        write!(f, " or {e}")?;
+                    } else {
+                        write!(f, ", {e}")?;
+                    }
+                }
+                Ok(())
+            }
+            FailureKind::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> PResult<'a, T>;
+
+    /// Transforms the parsed value.
+    fn map<U, F: Fn(T) -> U>(self, f: F) -> Map<Self, F, T>
+    where
+        Self: Sized,
+    {
+        Map {
+            parser: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Transforms the parsed value with a conversion that may fail, reporting
+    /// the failure against the span this parser consumed.
+    fn try_map<U, E: Display, F: Fn(T) -> Result<U, E>>(self, f: F) -> TryMap<Self, F, T>
+    where
+        Self: Sized,
+    {
+        TryMap {
+            parser: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Replaces the parsed value with `value`.
+    fn value<U: Clone>(self, value: U) -> Map<Self, impl Fn(T) -> U, T>
+    where
+        Self: Sized,
+    {
+        self.map(move |_| value.clone())
+    }
+
+    /// Tries `other` if this parser fails.
+    fn or<P: Parser<'a, T>>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+    {
+        Or {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+impl<'a> Parser<'a, &'a str> for &'static str {
+    fn parse(&self, input: &'a str) -> PResult<'a, &'a str> {
+        match input.strip_prefix(*self) {
+            Some(rest) => Ok((&input[..self.len()], rest)),
+            None => Err(Failure::expected(input, format!("{self:?}"))),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($parser:ident $value:ident),*) => {
+        impl<'a, $($parser, $value),*> Parser<'a, ($($value,)*)> for ($($parser,)*)
+        where
+            $($parser: Parser<'a, $value>),*
+        {
+            #[allow(non_snake_case)]
+            fn parse(&self, input: &'a str) -> PResult<'a, ($($value,)*)> {
+                let ($($parser,)*) = self;
+                $(let ($value, input) = $parser.parse(input)?;)*
+                Ok((($($value,)*), input))
+            }
+        }
+    };
+}
+
+impl_tuple!(A TA, B TB);
+impl_tuple!(A TA, B TB, C TC);
+impl_tuple!(A TA, B TB, C TC, D TD);
+impl_tuple!(A TA, B TB, C TC, D TD, E TE);
+impl_tuple!(A TA, B TB, C TC, D TD, E TE, F TF);
+impl_tuple!(A TA, B TB, C TC, D TD, E TE, F TF, G TG);
+impl_tuple!(A TA, B TB, C TC, D TD, E TE, F TF, G TG, H TH);
+
+pub struct Map<P, F, T> {
+    parser: P,
+    f: F,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, P, F, T, U> Parser<'a, U> for Map<P, F, T>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> U,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, U> {
+        let (value, rest) = self.parser.parse(input)?;
+        Ok(((self.f)(value), rest))
+    }
+}
+
+pub struct TryMap<P, F, T> {
+    parser: P,
+    f: F,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, P, F, T, U, E> Parser<'a, U> for TryMap<P, F, T>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> Result<U, E>,
+    E: Display,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, U> {
+        let (value, rest) = self.parser.parse(input)?;
+        match (self.f)(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(e) => Err(Failure::invalid(input, input.len() - rest.len(), e)),
+        }
+    }
+}
+
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<'a, A, B, T> Parser<'a, T> for Or<A, B>
+where
+    A: Parser<'a, T>,
+    B: Parser<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        match self.first.parse(input) {
+            Ok(result) => Ok(result),
+            Err(first) => self
+                .second
+                .parse(input)
+                .map_err(|second| first.merge(second)),
+        }
+    }
+}
+
+/// Matches `literal` exactly.
+pub fn literal<'a>(literal: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| Parser::parse(&literal, input)
+}
+
+/// Matches a single character satisfying `predicate`, described by
+/// `expected` in errors.
+pub fn char_if<'a>(
+    expected: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if predicate(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(Failure::expected(input, expected)),
+    }
+}
+
+/// Matches any single character.
+pub fn any_char<'a>() -> impl Parser<'a, char> {
+    char_if("any character", |_| true)
+}
+
+/// Matches zero or more characters satisfying `predicate`.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let len = input.find(|c| !predicate(c)).unwrap_or(input.len());
+        Ok(input.split_at(len))
+    }
+}
+
+/// Matches one or more characters satisfying `predicate`, described by
+/// `expected` in errors.
+pub fn take_while1<'a>(
+    expected: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    let inner = take_while(predicate);
+    move |input: &'a str| match inner.parse(input)? {
+        ("", _) => Err(Failure::expected(input, expected)),
+        result => Ok(result),
+    }
+}
+
+/// Matches a run of alphanumeric characters or underscores.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", |c| c.is_alphanumeric() || c == '_')
+}
+
+/// Matches an optionally signed decimal integer and converts it with
+/// `FromStr`, so out-of-range values are reported as errors.
+pub fn integer<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return Err(Failure::expected(input, "an integer"));
+        }
+        let (text, rest) = input.split_at(sign + digits);
+        match text.parse() {
+            Ok(value) => Ok((value, rest)),
+            Err(e) => Err(Failure::invalid(input, text.len(), e)),
+        }
+    }
+}
+
+/// Matches zero or more spaces or tabs. Newlines are left alone, since they
+/// usually separate records.
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    take_while(|c| c == ' ' || c == '\t')
+}
+
+/// Runs `parser`, skipping any spaces or tabs around it.
+pub fn ws<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    delimited(spaces(), parser, spaces())
+}
+
+/// Runs `prefix` then `parser`, keeping only the value of `parser`.
+pub fn preceded<'a, P, T>(
+    prefix: impl Parser<'a, P>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    (prefix, parser).map(|(_, value)| value)
+}
+
+/// Runs `parser` then `suffix`, keeping only the value of `parser`.
+pub fn terminated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, S>,
+) -> impl Parser<'a, T> {
+    (parser, suffix).map(|(value, _)| value)
+}
+
+/// Runs `open`, `parser` and `close`, keeping only the value of `parser`.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, T> {
+    (open, parser, close).map(|(_, value, _)| value)
+}
+
+/// Runs `parser` if it matches, without failing if it doesn't.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+fn repeat<'a, T>(parser: &impl Parser<'a, T>, mut input: &'a str, values: &mut Vec<T>) -> &'a str {
+    while let Ok((value, rest)) = parser.parse(input) {
+        // Stop on parsers that succeed without consuming anything
+        if rest.len() == input.len() {
+            break;
+        }
+        values.push(value);
+        input = rest;
+    }
+    input
+}
+
+/// Runs `parser` as many times as it matches, zero or more.
+pub fn many0<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut values = Vec::new();
+        let rest = repeat(&parser, input, &mut values);
+        Ok((values, rest))
+    }
+}
+
+/// Runs `parser` as many times as it matches, at least once.
+pub fn many1<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, input) = parser.parse(input)?;
+        let mut values = vec![first];
+        let rest = repeat(&parser, input, &mut values);
+        Ok((values, rest))
+    }
+}
+
+/// Runs `item` one or more times, with `separator` between each.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut input) = item.parse(input)?;
+        let mut values = vec![first];
+        loop {
+            let Ok((_, after_separator)) = separator.parse(input) else {
+                break;
+            };
+            let (value, rest) = item.parse(after_separator)?;
+            values.push(value);
+            input = rest;
+        }
+        Ok((values, input))
+    }
+}
+
+/// Runs `item` zero or more times, with `separator` between each.
+pub fn separated0<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    let list = separated(item, separator);
+    move |input: &'a str| match list.parse(input) {
+        Err(failure) if failure.at.len() == input.len() => Ok((Vec::new(), input)),
+        result => result,
+    }
+}
+
+/// Matches only at the end of the input.
+pub fn end<'a>() -> impl Parser<'a, ()> {
+    |input: &'a str| {
+        if input.is_empty() {
+            Ok(((), input))
+        } else {
+            Err(Failure::expected(input, "end of input"))
+        }
+    }
+}
+
+/// Matches the rest of the input.
+pub fn rest<'a>() -> impl Parser<'a, &'a str> {
+    |input: &'a str| Ok((input, &input[input.len()..]))
+}
+
+/// Parses all of `source` with `parser`, failing if any input is left over.
+pub fn parse<'a, T>(parser: impl Parser<'a, T>, source: &'a str) -> Result<T, ParseError> {
+    terminated(parser, end())
+        .parse(source)
+        .map(|(value, _)| value)
+        .map_err(|failure| failure.into_error(source))
+}
+
+/// Parses each line of `source` with `parser`, reporting failures against the
+/// line they occurred on.
+pub fn parse_lines<'a, T>(
+    parser: impl Parser<'a, T>,
+    source: &'a str,
+) -> Result<Vec<T>, ParseError> {
+    let parser = terminated(parser, end());
+    source
+        .lines()
+        .map(|line| {
+            parser
+                .parse(line)
+                .map(|(value, _)| value)
+                .map_err(|failure| {
+                    // Rebase the failure onto the full source so the line number is right
+                    let offset = line.as_ptr() as usize - source.as_ptr() as usize;
+                    let at = &source[offset + line.len() - failure.at.len()..];
+                    Failure { at, ..failure }.into_error(source)
+                })
+        })
+        .collect()
+}
+
+/// A type that can be parsed from text with a combinator grammar.
+pub trait Grammar: Sized {
+    fn grammar<'a>() -> impl Parser<'a, Self>;
+}
+
+/// Parses all of `s` with `T`'s grammar, for use in `FromStr`
+/// implementations.
+pub fn from_str<T: Grammar>(s: &str) -> Result<T, ParseError> {
+    parse(T::grammar(), s)
+}
+
+/// Reads all of `reader` and parses it with `T`'s grammar, for use in
+/// [`Input::parse`] implementations.
+pub fn read<R: BufRead, T: Grammar>(reader: R) -> Result<T> {
+    let source = String::parse(reader)?;
+    Ok(parse(T::grammar(), &source)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        delimited, integer, literal, many1, parse, parse_lines, preceded, separated, word, ws,
+        Parser,
+    };
+
+    #[test]
+    fn test_sequence() {
+        let sensor = (
+            preceded("Sensor at x=", integer::<i32>()),
+            preceded(", y=", integer::<i32>()),
+        );
+        assert_eq!(parse(sensor, "Sensor at x=2, y=-18").unwrap(), (2, -18));
+    }
+
+    #[test]
+    fn test_separated() {
+        let list = delimited("[", separated(ws(integer::<u8>()), ","), "]");
+        assert_eq!(parse(list, "[1, 2 ,3]").unwrap(), vec![1, 2, 3]);
+    }
+
+    fn operator<'a>() -> impl Parser<'a, char> {
+        literal("+").value('+').or(literal("*").value('*'))
+    }
+
+    #[test]
+    fn test_alternatives() {
+        assert_eq!(parse(operator(), "*").unwrap(), '*');
+
+        let error = parse(operator(), "-").unwrap_err();
+        assert_eq!(error.message(), "expected \"+\" or \"*\"");
+        assert_eq!(error.column(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse(integer::<u8>(), "300").unwrap_err();
+        assert_eq!(error.message(), "number too large to fit in target type");
+
+        let error = parse_lines(
+            ("move ", integer::<u32>(), " from ", word()),
+            "move 1 from a\nmove 2 to b",
+        )
+        .unwrap_err();
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 7);
+        assert_eq!(error.message(), "expected \" from \"");
+
+        let error = parse(many1(literal("ab")), "ababa").unwrap_err();
+        assert_eq!(error.column(), 5);
+        assert_eq!(error.message(), "expected end of input");
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{
+    bench::{self, BenchOptions, DaySamples},
+    params::{Params, Spec},
+    parse_failed, parse_input, solve_source, solve_source_ref, Answer, BorrowedInput, Buffer,
+    Input, InputRef, Part, SolveResult, Source,
+};
+use anyhow::{Error, Result};
+use std::{
+    any::{Any, TypeId},
+    fmt::Display,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+type ErasedSolve =
+    dyn Fn(Source, &Params, Option<Duration>) -> SolveResult<String, String> + Send + Sync;
+type ErasedBench = dyn Fn(&Path, &Params, &BenchOptions) -> Result<DaySamples> + Send + Sync;
+type ErasedLoad = dyn Fn(&Path) -> Result<Box<dyn Any>> + Send + Sync;
+type ErasedPart = dyn Fn(&dyn Any, Part, &Params) -> Result<String> + Send + Sync;
+type ErasedCommand = dyn Fn(&dyn Any, &[&str]) -> Result<String> + Send + Sync;
+
+/// A command a day registered for exploring its input in the REPL. Days can
+/// register commands without the `repl` feature, but nothing runs them.
+#[cfg_attr(not(feature = "repl"), allow(dead_code))]
+pub(crate) struct Command {
+    /// The command's name followed by the arguments it takes, like
+    /// `eval NAME`.
+    pub usage: &'static str,
+    pub help: &'static str,
+    pub run: Box<ErasedCommand>,
+}
+
+impl Command {
+    #[cfg_attr(not(feature = "repl"), allow(dead_code))]
+    pub fn name(&self) -> &'static str {
+        self.usage.split_whitespace().next().unwrap_or_default()
+    }
+}
+
+/// A single day's puzzle with its input type and part solvers erased so that
+/// days can be stored side by side and run by number.
+pub struct Day {
+    number: u32,
+    params: Vec<Spec>,
+    solve: Box<ErasedSolve>,
+    bench: Box<ErasedBench>,
+    /// The input type, which commands must take too. Inputs borrowed from
+    /// their buffer have none, so their days can't have commands.
+    input_type: Option<TypeId>,
+    load: Box<ErasedLoad>,
+    part: Box<ErasedPart>,
+    commands: Vec<Command>,
+}
+
+impl Day {
+    /// Parts can return any [`Answer`], including a `Result` of one if they
+    /// can fail.
+    pub fn new<I, P1, P2>(
+        number: u32,
+        solve_part_one: impl Fn(&I) -> P1 + Send + Sync + 'static,
+        solve_part_two: impl Fn(&I) -> P2 + Send + Sync + 'static,
+    ) -> Self
+    where
+        I: Input + Send + Sync + 'static,
+        P1: Answer + 'static,
+        P2: Answer + 'static,
+    {
+        Self::with_params(
+            number,
+            move |input: &I, _: &Params| solve_part_one(input),
+            move |input: &I, _: &Params| solve_part_two(input),
+        )
+    }
+
+    /// Like [`Day::new`], but the parts are also given the values of the
+    /// parameters declared with [`Day::param`].
+    pub fn with_params<I, P1, P2>(
+        number: u32,
+        solve_part_one: impl Fn(&I, &Params) -> P1 + Send + Sync + 'static,
+        solve_part_two: impl Fn(&I, &Params) -> P2 + Send + Sync + 'static,
+    ) -> Self
+    where
+        I: Input + Send + Sync + 'static,
+        P1: Answer + 'static,
+        P2: Answer + 'static,
+    {
+        let parts = Arc::new((solve_part_one, solve_part_two));
+        let bench_parts = parts.clone();
+        let repl_parts = parts.clone();
+        Self {
+            number,
+            params: Vec::new(),
+            solve: Box::new(move |source, params, timeout| {
+                let (part_one, part_two) = (parts.clone(), parts.clone());
+                let (params_one, params_two) = (params.clone(), params.clone());
+                solve_source(
+                    source,
+                    timeout,
+                    move |input: &I| (part_one.0)(input, &params_one).into_answer(),
+                    move |input: &I| (part_two.1)(input, &params_two).into_answer(),
+                )
+            }),
+            bench: Box::new(move |path, params, options| {
+                // Parse from memory both ways so only the parsing is timed
+                let buffer = Buffer::open(path)?;
+                let parse_stream = bench::measure(options, || I::parse(&buffer[..]));
+                let parse = bench::measure(options, || I::parse_bytes(&buffer));
+                if options.parse_only {
+                    return Ok(DaySamples {
+                        parse_stream: Some(parse_stream),
+                        parse,
+                        part_one: None,
+                        part_two: None,
+                    });
+                }
+                let input = parse_input::<I>(path)?;
+                Ok(DaySamples {
+                    parse_stream: Some(parse_stream),
+                    parse,
+                    part_one: Some(bench::measure(options, || (bench_parts.0)(&input, params))),
+                    part_two: Some(bench::measure(options, || (bench_parts.1)(&input, params))),
+                })
+            }),
+            input_type: Some(TypeId::of::<I>()),
+            load: Box::new(|path| Ok(Box::new(parse_input::<I>(path)?))),
+            part: Box::new(move |input, part, params| {
+                let input = input.downcast_ref::<I>().unwrap();
+                match part {
+                    Part::One => (repl_parts.0)(input, params).into_answer(),
+                    Part::Two => (repl_parts.1)(input, params).into_answer(),
+                }
+            }),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Like [`Day::with_params`], for an input that borrows from the buffer
+    /// it's read into instead of owning its data. `B` names the input type,
+    /// as described in [`BorrowedInput`].
+    ///
+    /// The REPL parses the input again each time it solves a part, and such
+    /// days can't have commands.
+    pub fn borrowing<B, P1, P2>(
+        number: u32,
+        solve_part_one: impl Fn(&B::Input<'_>, &Params) -> P1 + Send + Sync + 'static,
+        solve_part_two: impl Fn(&B::Input<'_>, &Params) -> P2 + Send + Sync + 'static,
+    ) -> Self
+    where
+        B: BorrowedInput,
+        P1: Answer + 'static,
+        P2: Answer + 'static,
+    {
+        let parts = Arc::new((solve_part_one, solve_part_two));
+        let bench_parts = parts.clone();
+        let repl_parts = parts.clone();
+        Self {
+            number,
+            params: Vec::new(),
+            solve: Box::new(move |source, params, timeout| {
+                let (part_one, part_two) = (parts.clone(), parts.clone());
+                let (params_one, params_two) = (params.clone(), params.clone());
+                solve_source_ref::<B, _, _>(
+                    source,
+                    timeout,
+                    &Arc::new(move |input: &B::Input<'_>| {
+                        (part_one.0)(input, &params_one).into_answer()
+                    }),
+                    &Arc::new(move |input: &B::Input<'_>| {
+                        (part_two.1)(input, &params_two).into_answer()
+                    }),
+                )
+            }),
+            bench: Box::new(move |path, params, options| {
+                // There's no stream to parse from, only the buffer
+                let buffer = Buffer::open(path)?;
+                let parse = bench::measure(options, || B::Input::parse_ref(&buffer));
+                if options.parse_only {
+                    return Ok(DaySamples {
+                        parse_stream: None,
+                        parse,
+                        part_one: None,
+                        part_two: None,
+                    });
+                }
+                let input =
+                    B::Input::parse_ref(&buffer).map_err(|e| parse_failed(e, Some(path)))?;
+                Ok(DaySamples {
+                    parse_stream: None,
+                    parse,
+                    part_one: Some(bench::measure(options, || (bench_parts.0)(&input, params))),
+                    part_two: Some(bench::measure(options, || (bench_parts.1)(&input, params))),
+                })
+            }),
+            input_type: None,
+            load: Box::new(|path| {
+                // The input can't be kept without its buffer, so keep that
+                let buffer = Buffer::open(path)?;
+                B::Input::parse_ref(&buffer).map_err(|e| parse_failed(e, Some(path)))?;
+                Ok(Box::new(buffer))
+            }),
+            part: Box::new(move |buffer, part, params| {
+                let buffer = buffer.downcast_ref::<Buffer>().unwrap();
+                let input = B::Input::parse_ref(buffer)?;
+                match part {
+                    Part::One => (repl_parts.0)(&input, params).into_answer(),
+                    Part::Two => (repl_parts.1)(&input, params).into_answer(),
+                }
+            }),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Declares a parameter the parts can read, and its value when none is
+    /// given. Parts must read it back as the same type as `default`.
+    pub fn param<T>(mut self, name: &'static str, default: T) -> Self
+    where
+        T: FromStr + Display + Send + Sync + 'static,
+        T::Err: Into<Error>,
+    {
+        self.params.push(Spec::new(name, default));
+        self
+    }
+
+    /// Adds a command for exploring the parsed input in the REPL. The first
+    /// word of `usage` names the command, and `run` is given the words after
+    /// it on the line.
+    ///
+    /// # Panics
+    ///
+    /// If `run` takes a different input type than the parts, or the day was
+    /// registered with [`Day::borrowing`].
+    pub fn command<I, R>(
+        mut self,
+        usage: &'static str,
+        help: &'static str,
+        run: impl Fn(&I, &[&str]) -> R + Send + Sync + 'static,
+    ) -> Self
+    where
+        I: 'static,
+        R: Answer,
+    {
+        assert!(
+            self.input_type.is_some(),
+            "day {} borrows its input, so command `{usage}` can't take it",
+            self.number
+        );
+        assert!(
+            Some(TypeId::of::<I>()) == self.input_type,
+            "command `{usage}` for day {} takes a different input than its parts",
+            self.number
+        );
+        self.commands.push(Command {
+            usage,
+            help,
+            run: Box::new(move |input, args| {
+                run(input.downcast_ref::<I>().unwrap(), args).into_answer()
+            }),
+        });
+        self
+    }
+
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn name(&self) -> String {
+        format!("day_{}", self.number)
+    }
+
+    /// The parameters the day declared, in the order they were declared.
+    pub fn param_specs(&self) -> &[Spec] {
+        &self.params
+    }
+
+    /// Resolves the values of the day's parameters, replacing defaults with
+    /// any `(name, value)` overrides for them.
+    pub fn params(&self, overrides: &[(String, String)]) -> Result<Params> {
+        Params::resolve(&self.params, overrides)
+    }
+
+    /// Solves both parts, giving each up to `timeout` to finish.
+    pub fn solve(
+        &self,
+        path: &Path,
+        params: &Params,
+        timeout: Option<Duration>,
+    ) -> SolveResult<String, String> {
+        (self.solve)(Source::Path(path), params, timeout)
+    }
+
+    /// Solves both parts of an input given as text rather than read from a
+    /// file.
+    pub fn solve_text(&self, text: &str, params: &Params) -> SolveResult<String, String> {
+        (self.solve)(Source::Text(text), params, None)
+    }
+
+    /// Parses an input for the REPL to run commands against.
+    #[cfg_attr(not(feature = "repl"), allow(dead_code))]
+    pub(crate) fn load(&self, path: &Path) -> Result<Box<dyn Any>> {
+        (self.load)(path)
+    }
+
+    /// Solves one part of an input parsed by [`Day::load`].
+    #[cfg_attr(not(feature = "repl"), allow(dead_code))]
+    pub(crate) fn solve_part(
+        &self,
+        input: &dyn Any,
+        part: Part,
+        params: &Params,
+    ) -> Result<String> {
+        (self.part)(input, part, params)
+    }
+
+    /// The commands the day registered, in the order they were registered.
+    #[cfg_attr(not(feature = "repl"), allow(dead_code))]
+    pub(crate) fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Parses the input once, then runs each stage repeatedly.
+    pub fn bench(
+        &self,
+        path: &Path,
+        params: &Params,
+        options: &BenchOptions,
+    ) -> Result<DaySamples> {
+        (self.bench)(path, params, options)
+    }
+}
+
+/// The set of days available to the runner, ordered by day number.
+#[derive(Default)]
+pub struct Registry {
+    days: Vec<Day>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a day to the registry, replacing any day with the same number.
+    pub fn register(&mut self, day: Day) {
+        match self.days.binary_search_by_key(&day.number, Day::number) {
+            Ok(index) => self.days[index] = day,
+            Err(index) => self.days.insert(index, day),
+        }
+    }
+
+    pub fn get(&self, number: u32) -> Option<&Day> {
+        self.days
+            .binary_search_by_key(&number, Day::number)
+            .ok()
+            .map(|index| &self.days[index])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Day> {
+        self.days.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
+
+impl FromIterator<Day> for Registry {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut result = Self::new();
+        for day in iter {
+            result.register(day);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Day;
+    use anyhow::Result;
+
+    #[allow(clippy::ptr_arg)]
+    fn add(input: &Vec<i32>, args: &[&str]) -> Result<i32> {
+        let n = args.first().unwrap_or(&"0").parse::<i32>()?;
+        Ok(input.iter().sum::<i32>() + n)
+    }
+
+    fn day() -> Day {
+        Day::new(1, |input: &Vec<i32>| input.len(), |_: &Vec<i32>| 0)
+    }
+
+    #[test]
+    fn test_command() {
+        let day = day().command("add [N]", "add N to the sum", add);
+        let [command] = day.commands() else {
+            panic!("expected one command");
+        };
+        assert_eq!(command.name(), "add");
+
+        let input = vec![1, 2];
+        assert_eq!((command.run)(&input, &["3"]).unwrap(), "6");
+        assert!((command.run)(&input, &["x"]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "takes a different input")]
+    fn test_command_input_type() {
+        day().command(
+            "first",
+            "the first line",
+            |input: &Vec<String>, _: &[&str]| input[0].clone(),
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! An interactive prompt for poking at a day's parsed input, for questions
+//! like how many valves have a nonzero flow rate that aren't worth writing
+//! code to answer.
+//!
+//! The runner starts one with `--repl`. The input is parsed once, and each
+//! line then runs a command against it: one of the built-in ones, or one the
+//! day registered with [`Day::command`]:
+//!
+//! ```ignore
+//! solve::Day::new(21, solve_part_one, solve_part_two)
+//!     .command("eval NAME [HUMN]", "evaluate a monkey", eval)
+//! ```
+//!
+//! Commands are completed with Tab, and lines are kept in a history that
+//! lasts between sessions.
+
+use crate::{
+    input_name,
+    registry::Command,
+    runner::{format_duration, Options},
+    Day, Params, Part, Registry,
+};
+use anyhow::{anyhow, Result};
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::FileHistory, validate::Validator, Context, Editor, Helper,
+};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::ExitCode,
+    time::Instant,
+};
+
+/// Where lines entered are kept between sessions.
+const HISTORY: &str = "target/repl_history";
+
+const BUILTINS: [(&str, &str); 4] = [
+    ("help", "list the commands"),
+    ("solve", "solve both parts of the input"),
+    ("reload", "parse the input again, after editing it"),
+    ("quit", "leave the REPL, as Ctrl-D does"),
+];
+
+/// Completes command names. Arguments are left alone, since only the day
+/// knows what they mean.
+struct Commands(Vec<&'static str>);
+
+impl Completer for Commands {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let before = &line[..pos];
+        let word = before.trim_start();
+        if word.contains(char::is_whitespace) {
+            return Ok((pos, Vec::new()));
+        }
+        let candidates = self.0.iter().filter(|name| name.starts_with(word));
+        Ok((
+            before.len() - word.len(),
+            candidates.map(|name| name.to_string()).collect(),
+        ))
+    }
+}
+
+impl Hinter for Commands {
+    type Hint = String;
+}
+
+impl Highlighter for Commands {}
+
+impl Validator for Commands {}
+
+impl Helper for Commands {}
+
+/// Runs `f`, turning a panic into an error so that one bad command doesn't
+/// end the session.
+fn catching(name: &str, f: impl FnOnce() -> Result<String>) -> Result<String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err(anyhow!("`{name}` panicked")))
+}
+
+fn print_help(day: &Day) {
+    let usages = BUILTINS
+        .into_iter()
+        .chain(day.commands().iter().map(|c| (c.usage, c.help)))
+        .collect::<Vec<_>>();
+    let width = usages.iter().map(|(usage, _)| usage.len()).max().unwrap();
+    for (usage, help) in usages {
+        println!("  {usage:width$}  {help}");
+    }
+}
+
+fn solve(day: &Day, input: &dyn Any, params: &Params) {
+    for part in [Part::One, Part::Two] {
+        let start = Instant::now();
+        let result = catching("solve", || day.solve_part(input, part, params));
+        let elapsed = format_duration(start.elapsed());
+        match result {
+            Ok(answer) if answer.contains('\n') => println!("Part {part} ({elapsed}):\n{answer}"),
+            Ok(answer) => println!("Part {part}: {answer} ({elapsed})"),
+            Err(e) => eprintln!("Part {part} failed: {e:#}"),
+        }
+    }
+}
+
+/// Reads and runs commands against `day`'s input at `path` until the user
+/// quits.
+fn run(day: &Day, path: &Path, params: &Params) -> Result<()> {
+    let mut input = day.load(path)?;
+    println!(
+        "Parsed {} for day {}. Enter `help` to list the commands.",
+        path.display(),
+        day.number()
+    );
+
+    let names = BUILTINS.iter().map(|&(name, _)| name);
+    let mut editor = Editor::<Commands, FileHistory>::new()?;
+    editor.set_helper(Some(Commands(
+        names
+            .chain(day.commands().iter().map(Command::name))
+            .collect(),
+    )));
+    // There's no history until the first session ends
+    let _ = editor.load_history(HISTORY);
+
+    let prompt = format!("{}> ", day.name());
+    loop {
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&name, args)) = words.split_first() else {
+            continue;
+        };
+        editor.add_history_entry(line.as_str())?;
+
+        match name {
+            "help" => print_help(day),
+            "solve" => solve(day, &*input, params),
+            "reload" => match day.load(path) {
+                Ok(reloaded) => {
+                    input = reloaded;
+                    println!("Parsed {} again", path.display());
+                }
+                Err(e) => eprintln!("error: {e:#}"),
+            },
+            "quit" | "exit" => break,
+            name => match day.commands().iter().find(|c| c.name() == name) {
+                Some(command) => match catching(name, || (command.run)(&*input, args)) {
+                    Ok(output) => println!("{output}"),
+                    Err(e) => eprintln!("error: {e:#}"),
+                },
+                None => eprintln!("unknown command `{name}`, enter `help` to list the commands"),
+            },
+        }
+    }
+
+    // Losing the history isn't worth failing over
+    let _ = editor.save_history(HISTORY);
+    Ok(())
+}
+
+/// Starts a REPL for the one day selected, as the runner does for `--repl`.
+pub(crate) fn main(registry: &Registry, options: &Options) -> ExitCode {
+    let day = registry.get(options.days[0]).unwrap();
+    let result = options.input_paths(day).and_then(|paths| {
+        let input = input_name(&paths[0]);
+        let (overrides, _) = options.answers()?.overrides(day, &input, &options.params);
+        let params = day.params(&overrides)?;
+        run(day, &paths[0], &params)
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
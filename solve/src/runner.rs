@@ -0,0 +1,728 @@
+#[cfg(feature = "export")]
+use crate::export::{self, ExportOptions};
+#[cfg(feature = "repl")]
+use crate::repl;
+use crate::{
+    alloc::{self, format_bytes},
+    bench,
+    bench::BenchOptions,
+    buffer::ReadError,
+    input_name, inputs,
+    output::{self, Format, Record},
+    par::Pool,
+    params,
+    trace::{self, Directive},
+    viz::{self, Playback},
+    Answers, Day, Part, Registry, Solution, Status,
+};
+use anyhow::{bail, Context, Error, Result};
+use std::{
+    env,
+    fs::File,
+    io::{self, LineWriter},
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+const USAGE: &str = "\
+usage: aoc [DAYS...] [OPTIONS]
+
+DAYS may be a day number (`5`), an inclusive range (`3-7`) or `all`.
+Every registered day is run when no days are given.
+
+A day's inputs are the `*.input` files in `inputs/day_N/` and `day_N/`,
+and are named by their file name. Each day runs against its `test.input`,
+or against every input found for it if it has no `test.input`.
+
+options:
+  --input NAME            name of the input file to run each day against (default: see above)
+  --all-inputs            run each day against every input found for it
+  --answers PATH          expected answers to check results against (default: answers.txt, if present)
+  --jobs N                run up to N days at once (default: 1)
+  --timeout SECONDS       give up on a part after this long (default: no limit)
+  --format FORMAT         print results as `text`, `json` or `csv` (default: text)
+  --param NAME=VALUE      set a parameter of the days that declare it, over any the answers give an
+                          input (may be repeated)
+  --trace TARGET[=LEVEL]  write the events a day traces, e.g. `day_10` or `all=debug` (may be repeated)
+  --trace-file PATH       write traced events to PATH instead of stderr
+  --visualize             watch the days that draw their simulations in the terminal
+  --export PATH           save the frames days draw as a GIF (`*.gif`), an animated PNG (`*.png`)
+                          or a directory of PNGs (anything else)
+  --export-every N        only save every Nth frame (default: 1)
+  --fps N                 frames shown per second, or 0 for no limit (default: 30)
+  --step                  wait for Enter before each frame when visualizing
+  --repl                  explore a day's parsed input with commands instead of solving it
+  --help                  print this message
+
+benchmarking:
+  --bench                 run each part repeatedly and report timing statistics
+  --warmup N              untimed runs before sampling (default: 3)
+  --samples N             timed runs per stage (default: 20)
+  --parse-only            only time parsing, for inputs too large to solve
+  --save-baseline PATH    save the results as a baseline
+  --baseline PATH         compare the results against a saved baseline
+
+exit status:
+  0  every day ran, and every answer checked was correct
+  1  the command line or answers file was invalid
+  2  an input was missing or couldn't be read
+  3  an input couldn't be parsed
+  4  a part returned an error, panicked or timed out
+  5  an answer didn't match the expected one
+When days fail in different ways, the lowest of these statuses is used.";
+
+/// The ways running a day can fail, each with its own exit status. They're
+/// ordered so that the failure reported when there are several is the
+/// least of them.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Failure {
+    MissingInput,
+    Parse,
+    Solve,
+    Mismatch,
+}
+
+impl Failure {
+    /// Classifies an error from reading or parsing a day's input.
+    pub fn of_input(e: &Error) -> Self {
+        if e.downcast_ref::<ReadError>().is_some() {
+            Failure::MissingInput
+        } else {
+            Failure::Parse
+        }
+    }
+
+    /// How a part failed, or `None` if it didn't.
+    pub fn of_part<T>(solution: &Solution<T>) -> Option<Self> {
+        if solution.error().is_some() || solution.panicked().is_some() || solution.timed_out() {
+            Some(Failure::Solve)
+        } else if let Some(Status::Mismatch { .. }) = solution.status() {
+            Some(Failure::Mismatch)
+        } else {
+            None
+        }
+    }
+
+    pub fn exit_code(self) -> ExitCode {
+        ExitCode::from(match self {
+            Failure::MissingInput => 2,
+            Failure::Parse => 3,
+            Failure::Solve => 4,
+            Failure::Mismatch => 5,
+        })
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Options {
+    pub days: Vec<u32>,
+    pub input: Option<String>,
+    pub all_inputs: bool,
+    pub answers: Option<String>,
+    pub jobs: Option<NonZeroUsize>,
+    pub timeout: Option<Duration>,
+    pub format: Format,
+    pub params: Vec<(String, String)>,
+    pub trace: Vec<Directive>,
+    pub trace_file: Option<String>,
+    pub visualize: Option<Playback>,
+    #[cfg(feature = "export")]
+    pub export: Option<(String, ExportOptions)>,
+    pub repl: bool,
+    pub bench: Option<BenchOptions>,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+}
+
+impl Options {
+    fn parse(registry: &Registry, mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut bench = BenchOptions::default();
+        let mut benchmarking = false;
+        let mut playback = Playback::default();
+        let mut visualizing = false;
+        let mut export_path = None;
+        let mut export_every = None;
+        let mut fps_given = false;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("expected a value after {arg}"))
+            };
+            match arg.as_str() {
+                "--input" => options.input = Some(value()?),
+                "--all-inputs" => options.all_inputs = true,
+                "--answers" => options.answers = Some(value()?),
+                "--jobs" => {
+                    let jobs = value()?.parse()?;
+                    options.jobs =
+                        Some(NonZeroUsize::new(jobs).context("--jobs must be at least 1")?);
+                }
+                "--timeout" => {
+                    let seconds = value()?.parse()?;
+                    options.timeout =
+                        Some(Duration::try_from_secs_f64(seconds).context("invalid --timeout")?);
+                }
+                "--format" => options.format = value()?.parse()?,
+                "--param" => options.params.push(params::parse_assignment(&value()?)?),
+                "--trace" => options.trace.push(value()?.parse()?),
+                "--trace-file" => options.trace_file = Some(value()?),
+                "--visualize" => visualizing = true,
+                #[cfg(feature = "repl")]
+                "--repl" => options.repl = true,
+                #[cfg(not(feature = "repl"))]
+                "--repl" => bail!("--repl needs the `repl` feature"),
+                "--export" => export_path = Some(value()?),
+                "--export-every" => {
+                    let every: usize = value()?.parse()?;
+                    if every == 0 {
+                        bail!("--export-every must be at least 1");
+                    }
+                    export_every = Some(every);
+                }
+                "--fps" => {
+                    fps_given = true;
+                    playback.fps = value()?.parse()?;
+                    if !playback.fps.is_finite() || playback.fps < 0.0 {
+                        bail!("--fps must be a positive number or 0");
+                    }
+                }
+                "--step" => playback.step = true,
+                "--bench" => benchmarking = true,
+                "--warmup" => bench.warmup = value()?.parse()?,
+                "--samples" => bench.samples = value()?.parse()?,
+                "--parse-only" => bench.parse_only = true,
+                "--save-baseline" => options.save_baseline = Some(value()?),
+                "--baseline" => options.baseline = Some(value()?),
+                "all" => options.days.extend(registry.iter().map(Day::number)),
+                x if x.starts_with('-') => bail!("unrecognized option `{x}`"),
+                x => {
+                    let (first, last) = match x.split_once('-') {
+                        Some((first, last)) => (first.parse()?, last.parse()?),
+                        None => {
+                            let day = x.parse()?;
+                            (day, day)
+                        }
+                    };
+                    for day in first..=last {
+                        if registry.get(day).is_none() {
+                            bail!("day {day} is not registered");
+                        }
+                        options.days.push(day);
+                    }
+                }
+            }
+        }
+
+        if options.days.is_empty() {
+            options.days.extend(registry.iter().map(Day::number));
+        }
+        if options.all_inputs && options.input.is_some() {
+            bail!("--input can't be used with --all-inputs");
+        }
+
+        let selected = options.days.iter().map(|&day| registry.get(day).unwrap());
+        for (name, _) in options.params.iter() {
+            let mut specs = selected.clone().flat_map(Day::param_specs);
+            if !specs.any(|spec| spec.name() == name) {
+                bail!("none of the selected days have a parameter named `{name}`");
+            }
+        }
+        for day in selected {
+            day.params(&options.params)
+                .with_context(|| format!("invalid parameters for day {}", day.number()))?;
+        }
+        for target in options.trace.iter().filter_map(Directive::target) {
+            if !registry.iter().any(|day| day.name() == target) {
+                bail!("can't trace `{target}`, expected a day like `day_10` or `all`");
+            }
+        }
+        if options.trace_file.is_some() && options.trace.is_empty() {
+            bail!("--trace-file can only be used with --trace");
+        }
+        if visualizing || export_path.is_some() {
+            if options.jobs.is_some() {
+                bail!("--jobs can't be used while drawing frames, since frames from different days would mix");
+            }
+            if benchmarking {
+                bail!("--bench can't be used while drawing frames");
+            }
+        } else if fps_given {
+            bail!("--fps can only be used with --visualize or --export");
+        }
+        if visualizing {
+            if options.format != Format::Text {
+                bail!("--visualize can only be used when printing results as text");
+            }
+            options.visualize = Some(playback);
+        } else if playback.step {
+            bail!("--step can only be used with --visualize");
+        }
+        let exporting = export_path.is_some();
+        match export_path {
+            #[cfg(feature = "export")]
+            Some(path) => {
+                let defaults = ExportOptions::default();
+                let export = ExportOptions {
+                    fps: if fps_given {
+                        playback.fps
+                    } else {
+                        defaults.fps
+                    },
+                    every: export_every.unwrap_or(defaults.every),
+                };
+                options.export = Some((path, export));
+            }
+            #[cfg(not(feature = "export"))]
+            Some(_) => bail!("--export needs the `export` feature"),
+            None if export_every.is_some() => {
+                bail!("--export-every can only be used with --export")
+            }
+            None => (),
+        }
+        if benchmarking {
+            if bench.samples == 0 {
+                bail!("--samples must be at least 1");
+            }
+            if options.jobs.is_some() {
+                bail!("--jobs can't be used with --bench, since parallel runs skew timings");
+            }
+            if options.timeout.is_some() {
+                bail!("--timeout can't be used with --bench");
+            }
+            if options.format != Format::Text {
+                bail!("--format can't be used with --bench");
+            }
+            if !options.trace.is_empty() {
+                bail!("--trace can't be used with --bench, since writing events skews timings");
+            }
+            options.bench = Some(bench);
+        } else if options.save_baseline.is_some() || options.baseline.is_some() {
+            bail!("baselines can only be used with --bench");
+        }
+
+        if options.repl {
+            if options.days.len() != 1 {
+                bail!("--repl needs exactly one day, like `aoc 16 --repl`");
+            }
+            if options.all_inputs || options.bench.is_some() || exporting {
+                bail!("--repl can't be used with --all-inputs, --bench or --export");
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// The answers given with `--answers`, or the default ones. Besides being
+    /// checked, they decide which parameters each input is solved with.
+    pub fn answers(&self) -> Result<Answers> {
+        match &self.answers {
+            Some(path) => Answers::load(Path::new(path)),
+            None => Answers::load_default(),
+        }
+    }
+
+    /// The paths of the input files to run `day` against.
+    pub fn input_paths(&self, day: &Day) -> Result<Vec<PathBuf>> {
+        if self.all_inputs {
+            let paths = inputs::discover(day)?;
+            if paths.is_empty() {
+                bail!("no inputs found for day {}", day.number());
+            }
+            return Ok(paths);
+        }
+        match &self.input {
+            Some(name) => Ok(vec![
+                inputs::find(day, name).unwrap_or_else(|| Path::new(&day.name()).join(name))
+            ]),
+            None => inputs::defaults(day),
+        }
+    }
+
+    /// Every day and input to run, in order.
+    pub fn runs(&self, registry: &Registry) -> Result<Vec<(u32, PathBuf)>> {
+        let mut runs = Vec::new();
+        for &number in self.days.iter() {
+            let day = registry.get(number).unwrap();
+            runs.extend(
+                self.input_paths(day)?
+                    .into_iter()
+                    .map(|path| (number, path)),
+            );
+        }
+        Ok(runs)
+    }
+}
+
+pub(crate) fn format_duration(duration: Duration) -> String {
+    format!("{duration:.3?}")
+}
+
+/// A part's duration, marked if it can't be trusted.
+fn duration_cell(solution: &Solution<String>) -> String {
+    let duration = format_duration(solution.duration());
+    if solution.tainted() {
+        format!("{duration} *")
+    } else {
+        duration
+    }
+}
+
+fn answer_cell(
+    day: u32,
+    input: &str,
+    part: Part,
+    solution: &Solution<String>,
+    notes: &mut Vec<String>,
+) -> String {
+    if solution.error().is_some() {
+        return "error (see below)".to_string();
+    }
+    if solution.panicked().is_some() {
+        return "panicked (see below)".to_string();
+    }
+    let Some(answer) = solution.result() else {
+        return "timed out".to_string();
+    };
+    if let Some(Status::Mismatch { expected }) = solution.status() {
+        notes.push(format!(
+            "Day {day} part {part} ({input}) mismatch:\n  expected: {expected}\n  actual:   {answer}"
+        ));
+    }
+    if answer.contains('\n') {
+        notes.push(format!(
+            "Day {day} part {part} ({input}):\n{}",
+            answer.trim_start_matches('\n')
+        ));
+        "(see below)".to_string()
+    } else {
+        answer.clone()
+    }
+}
+
+const COLUMNS: [&str; 9] = [
+    "Day", "Input", "Parse", "Part one", "Time", "Part two", "Time", "Total", "Status",
+];
+
+/// A row for a day that didn't produce any answers, with `message` in place of
+/// the part one answer.
+fn failure_row(day: u32, input: &str, message: &str, status: String) -> Vec<String> {
+    let mut row = vec![String::new(); COLUMNS.len()];
+    row[0] = day.to_string();
+    row[1] = input.to_string();
+    row[3] = message.to_string();
+    row[COLUMNS.len() - 1] = status;
+    row
+}
+
+/// Summarizes the status of both parts of a day, or `None` if neither part
+/// was checked.
+fn day_status(part_one: &Solution<String>, part_two: &Solution<String>) -> Option<&'static str> {
+    if part_one.timed_out() || part_two.timed_out() {
+        return Some("timeout");
+    }
+    if part_one.panicked().is_some() || part_two.panicked().is_some() {
+        return Some("panicked");
+    }
+    if part_one.error().is_some() || part_two.error().is_some() {
+        return Some("error");
+    }
+    match (part_one.status(), part_two.status()) {
+        (None, None) => None,
+        (Some(Status::Mismatch { .. }), _) | (_, Some(Status::Mismatch { .. })) => Some("mismatch"),
+        _ => Some("pass"),
+    }
+}
+
+pub(crate) fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = usize::max(*width, cell.len());
+        }
+    }
+
+    let print_row = |cells: &mut dyn Iterator<Item = &str>| {
+        let line = cells
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    print_row(&mut header.iter().copied());
+    println!(
+        "{}",
+        "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+    );
+    for row in rows {
+        print_row(&mut row.iter().map(String::as_str));
+    }
+}
+
+/// Lists the parameters each day declares, for `--help`.
+fn print_params(registry: &Registry) {
+    let rows = registry
+        .iter()
+        .flat_map(|day| {
+            day.param_specs().iter().map(|spec| {
+                vec![
+                    day.number().to_string(),
+                    spec.name().to_string(),
+                    spec.type_name().to_string(),
+                    spec.default().to_string(),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    if !rows.is_empty() {
+        println!("\nparameters:");
+        print_table(&["Day", "Name", "Type", "Default"], &rows);
+    }
+}
+
+/// Runs the days selected on the command line and prints a summary table of
+/// their answers and timings.
+pub fn main(registry: &Registry) -> ExitCode {
+    if env::args().any(|arg| arg == "--help") {
+        println!("{USAGE}");
+        print_params(registry);
+        return ExitCode::SUCCESS;
+    }
+    let options = match Options::parse(registry, env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {e:#}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if options.bench.is_some() {
+        return bench::main(registry, &options);
+    }
+    if let Some(playback) = options.visualize {
+        viz::init(playback);
+    }
+    #[cfg(feature = "export")]
+    if let Some((path, export_options)) = &options.export {
+        export::start(path, *export_options);
+    }
+    if !options.trace.is_empty() {
+        let directives = options.trace.clone();
+        match &options.trace_file {
+            Some(path) => match File::create(path) {
+                Ok(file) => trace::init(directives, LineWriter::new(file)),
+                Err(e) => {
+                    eprintln!("error: failed to create {path}: {e}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            None => trace::init(directives, io::stderr()),
+        }
+    }
+    #[cfg(feature = "repl")]
+    if options.repl {
+        return repl::main(registry, &options);
+    }
+
+    let answers = match options.answers() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = match options.runs(registry) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return Failure::MissingInput.exit_code();
+        }
+    };
+
+    let mut rows = Vec::new();
+    let mut records = Vec::new();
+    let mut memory_rows = Vec::new();
+    let mut notes = Vec::new();
+    let mut errors = Vec::new();
+    let mut failures = Vec::new();
+    let start = Instant::now();
+
+    // Days run on the pool but are reported in order once they've all finished
+    let pool = Pool::new(options.jobs.unwrap_or(NonZeroUsize::MIN));
+    let results = pool.map(runs, |(number, path)| {
+        let day = registry.get(number).unwrap();
+        let (overrides, checked) = answers.overrides(day, &input_name(&path), &options.params);
+        // Parts catch their own panics, but parsing can panic too, and that
+        // shouldn't stop the remaining days from running
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let params = day.params(&overrides)?;
+            day.solve(&path, &params, options.timeout)
+        }));
+        (number, path.display().to_string(), checked, result)
+    });
+    // Only parts run under a token, never the runner itself
+    let results = results.expect("the runner was cancelled");
+
+    // Allocations from days running at the same time can't be told apart
+    let parallel = options.jobs.is_some_and(|jobs| jobs.get() > 1);
+
+    for (number, path, checked, result) in results {
+        let input = input_name(Path::new(&path));
+        let failure_status = || {
+            if checked && answers.has_day(number) {
+                "fail".to_string()
+            } else {
+                String::new()
+            }
+        };
+
+        match result {
+            Ok(Ok(mut report)) => {
+                if checked {
+                    answers.check(number, &input, Part::One, &mut report.part_one);
+                    answers.check(number, &input, Part::Two, &mut report.part_two);
+                }
+                let status = day_status(&report.part_one, &report.part_two);
+
+                for (part, solution) in
+                    [(Part::One, &report.part_one), (Part::Two, &report.part_two)]
+                {
+                    failures.extend(Failure::of_part(solution));
+                    if let Some(e) = solution.error() {
+                        errors.push(format!("Day {number} part {part} ({input}) failed: {e:#}"));
+                    }
+                    if let Some(message) = solution.panicked() {
+                        errors.push(format!(
+                            "Day {number} part {part} ({input}) panicked: {message}"
+                        ));
+                    }
+                    let mut record = Record::new(number, part, &path, solution);
+                    if parallel {
+                        record.memory = None;
+                    }
+                    records.push(record);
+                }
+
+                for (stage, memory) in [
+                    ("parse", report.parse_memory),
+                    ("one", report.part_one.memory()),
+                    ("two", report.part_two.memory()),
+                ] {
+                    if let Some(memory) = memory {
+                        memory_rows.push(vec![
+                            number.to_string(),
+                            input.clone(),
+                            stage.to_string(),
+                            format_bytes(memory.peak),
+                            format_bytes(memory.allocated),
+                            memory.allocations.to_string(),
+                        ]);
+                    }
+                }
+
+                rows.push(vec![
+                    number.to_string(),
+                    input.clone(),
+                    format_duration(report.parse_duration),
+                    answer_cell(number, &input, Part::One, &report.part_one, &mut notes),
+                    duration_cell(&report.part_one),
+                    answer_cell(number, &input, Part::Two, &report.part_two, &mut notes),
+                    duration_cell(&report.part_two),
+                    format_duration(report.total_duration()),
+                    status.unwrap_or_default().to_string(),
+                ]);
+            }
+            Ok(Err(e)) => {
+                failures.push(Failure::of_input(&e));
+                errors.push(format!("Day {number} ({input}) failed: {e:#}"));
+                rows.push(failure_row(
+                    number,
+                    &input,
+                    "error (see below)",
+                    failure_status(),
+                ));
+                for part in [Part::One, Part::Two] {
+                    records.push(Record::failed(number, part, &path, "error"));
+                }
+            }
+            Err(_) => {
+                failures.push(Failure::Solve);
+                rows.push(failure_row(number, &input, "panicked", failure_status()));
+                for part in [Part::One, Part::Two] {
+                    records.push(Record::failed(number, part, &path, "panicked"));
+                }
+            }
+        }
+    }
+
+    // Frames are only written once every day has drawn them
+    #[cfg(not(feature = "export"))]
+    let export_failed = false;
+    #[cfg(feature = "export")]
+    let export_failed = match export::finish() {
+        Ok(Some((path, frames))) => {
+            let note = format!("Exported {frames} frames to {}", path.display());
+            match options.format {
+                Format::Text => notes.push(note),
+                _ => eprintln!("{note}"),
+            }
+            false
+        }
+        Ok(None) => false,
+        Err(e) => {
+            errors.push(format!("error: {e:#}"));
+            true
+        }
+    };
+
+    if options.format != Format::Text {
+        match options.format {
+            Format::Json => println!("{}", output::to_json(&records)),
+            _ => print!("{}", output::to_csv(&records)),
+        }
+        // Keep stdout parseable, but don't lose the reasons days failed
+        for error in errors {
+            eprintln!("{error}");
+        }
+        return exit_code(&failures, export_failed);
+    }
+
+    print_table(&COLUMNS, &rows);
+    println!("\nTotal: {}", format_duration(start.elapsed()));
+    if records.iter().any(|record| record.tainted) {
+        println!(
+            "* measured while a part that timed out was still running, since it doesn't check \
+             for cancellation, so it may be slower than it should be"
+        );
+    }
+    if alloc::is_installed() {
+        println!();
+        if parallel {
+            println!("Memory isn't reported when running days in parallel");
+        } else {
+            print_table(
+                &["Day", "Input", "Stage", "Peak", "Allocated", "Allocations"],
+                &memory_rows,
+            );
+        }
+    }
+    for note in notes.into_iter().chain(errors) {
+        println!("\n{note}");
+    }
+
+    exit_code(&failures, export_failed)
+}
+
+fn exit_code(failures: &[Failure], export_failed: bool) -> ExitCode {
+    match failures.iter().min() {
+        Some(failure) => failure.exit_code(),
+        None if export_failed => ExitCode::FAILURE,
+        None => ExitCode::SUCCESS,
+    }
+}
@@ -0,0 +1,532 @@
+//! Generic state-space searches over a user-supplied successor function.
+//!
+//! [`bfs`], [`dijkstra`] and [`astar`] find a cheapest path to a goal and
+//! reconstruct it. [`traverse`] visits every reachable state breadth-first,
+//! and [`branch_and_bound`] looks for the state with the highest value. The
+//! last two take a [`Prune`] to skip states that are already covered by ones
+//! seen earlier.
+//!
+//! Every search stops early, as if it had run out of states, once the current
+//! part is [cancelled](crate::cancel).
+
+use crate::cancel;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::{self, Display},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Counters describing how much work a search did.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// States whose successors were generated.
+    pub expanded: usize,
+    /// Successors added to the frontier.
+    pub generated: usize,
+    /// The most states waiting in the frontier at once.
+    pub peak_frontier: usize,
+}
+
+impl Stats {
+    fn generate(&mut self, frontier: usize) {
+        self.generated += 1;
+        self.peak_frontier = self.peak_frontier.max(frontier);
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} expanded, {} generated, peak frontier {}",
+            self.expanded, self.generated, self.peak_frontier
+        )
+    }
+}
+
+/// A path from the start state to a goal, including both ends.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// The result of a path search.
+#[derive(Clone, Debug)]
+pub struct Outcome<S, C> {
+    /// The cheapest path to a goal, or `None` if no goal is reachable.
+    pub path: Option<Path<S, C>>,
+    pub stats: Stats,
+}
+
+/// The result of a [`branch_and_bound`] search.
+#[derive(Clone, Debug)]
+pub struct Best<S, V> {
+    /// The highest-valued state found and its value.
+    pub best: Option<(S, V)>,
+    pub stats: Stats,
+}
+
+/// Every state reached by a path search, with its parent and the cheapest
+/// known cost to reach it.
+struct Tree<S, C> {
+    nodes: Vec<(S, Option<usize>, C)>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Tree<S, C> {
+    fn new(start: S, cost: C) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, None, cost)],
+        }
+    }
+
+    /// Records that `state` can be reached from `parent` for `cost`, returning
+    /// its index if that's cheaper than any path found before.
+    fn relax(&mut self, state: S, parent: usize, cost: C) -> Option<usize> {
+        match self.index.get(&state) {
+            Some(&i) if self.nodes[i].2 <= cost => None,
+            Some(&i) => {
+                self.nodes[i].1 = Some(parent);
+                self.nodes[i].2 = cost;
+                Some(i)
+            }
+            None => {
+                let i = self.nodes.len();
+                self.index.insert(state.clone(), i);
+                self.nodes.push((state, Some(parent), cost));
+                Some(i)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<S, C> {
+        let mut states = Vec::new();
+        let mut current = Some(goal);
+        while let Some(i) = current {
+            states.push(self.nodes[i].0.clone());
+            current = self.nodes[i].1;
+        }
+        states.reverse();
+        Path {
+            states,
+            cost: self.nodes[goal].2,
+        }
+    }
+}
+
+/// Finds a path to a goal with the fewest steps.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut tree = Tree::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if cancel::is_cancelled() {
+            break;
+        }
+        if is_goal(&tree.nodes[i].0) {
+            return Outcome {
+                path: Some(tree.path(i)),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        let cost = tree.nodes[i].2 + 1;
+        for next in successors(&tree.nodes[i].0) {
+            if let Some(j) = tree.relax(next, i, cost) {
+                queue.push_back(j);
+                stats.generate(queue.len());
+            }
+        }
+    }
+
+    Outcome { path: None, stats }
+}
+
+/// Finds a cheapest path to a goal, where `successors` yields each neighbour
+/// along with the cost of stepping to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Finds a cheapest path to a goal, expanding states in order of their cost
+/// so far plus `heuristic`.
+///
+/// The heuristic must never overestimate the remaining cost, or the path
+/// found may not be the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut stats = Stats::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut tree = Tree::new(start, C::default());
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cancel::is_cancelled() {
+            break;
+        }
+        // A cheaper path to this state was found after this entry was queued
+        if cost > tree.nodes[i].2 {
+            continue;
+        }
+        if is_goal(&tree.nodes[i].0) {
+            return Outcome {
+                path: Some(tree.path(i)),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        for (next, step) in successors(&tree.nodes[i].0) {
+            let estimate = heuristic(&next);
+            let next_cost = cost + step;
+            if let Some(j) = tree.relax(next, i, next_cost) {
+                heap.push(Reverse((next_cost + estimate, next_cost, j)));
+                stats.generate(heap.len());
+            }
+        }
+    }
+
+    Outcome { path: None, stats }
+}
+
+/// Decides which states a [`traverse`] or [`branch_and_bound`] search goes on
+/// to expand.
+pub trait Prune<S> {
+    /// Returns whether `state` is worth expanding, recording it as seen.
+    fn admit(&mut self, state: &S) -> bool;
+}
+
+/// Expands every state, for searches that can't revisit states.
+pub struct NoPrune;
+
+impl<S> Prune<S> for NoPrune {
+    fn admit(&mut self, _state: &S) -> bool {
+        true
+    }
+}
+
+/// Expands each distinct state once.
+impl<S: Clone + Eq + Hash> Prune<S> for HashSet<S> {
+    fn admit(&mut self, state: &S) -> bool {
+        self.insert(state.clone())
+    }
+}
+
+/// Skips states that are no better than one seen earlier.
+///
+/// `split` divides a state into a key and a value. Only states with equal keys
+/// are compared, and a state is skipped if an earlier one's value dominates
+/// its own. `dominates(a, b)` must return whether `a` is at least as good as
+/// `b` in every respect, and so must hold when `a == b`.
+pub struct Dominance<K, V, F, D> {
+    split: F,
+    dominates: D,
+    seen: HashMap<K, Vec<V>>,
+}
+
+impl<K, V, F, D> Dominance<K, V, F, D> {
+    pub fn new(split: F, dominates: D) -> Self {
+        Self {
+            split,
+            dominates,
+            seen: HashMap::new(),
+        }
+    }
+}
+
+impl<S, K, V, F, D> Prune<S> for Dominance<K, V, F, D>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> (K, V),
+    D: FnMut(&V, &V) -> bool,
+{
+    fn admit(&mut self, state: &S) -> bool {
+        let (key, value) = (self.split)(state);
+        // Only the values not dominated by any other are kept for each key
+        let front = self.seen.entry(key).or_default();
+        if front.iter().any(|seen| (self.dominates)(seen, &value)) {
+            return false;
+        }
+        front.retain(|seen| !(self.dominates)(&value, seen));
+        front.push(value);
+        true
+    }
+}
+
+/// Iterates over the states reachable from a start state in breadth-first
+/// order, skipping states rejected by a [`Prune`].
+pub struct Traversal<S, F, P> {
+    queue: VecDeque<S>,
+    successors: F,
+    prune: P,
+    stats: Stats,
+}
+
+impl<S, F, P> Traversal<S, F, P> {
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+impl<S, I, F, P> Iterator for Traversal<S, F, P>
+where
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    P: Prune<S>,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        if cancel::is_cancelled() {
+            return None;
+        }
+        while let Some(state) = self.queue.pop_front() {
+            if !self.prune.admit(&state) {
+                continue;
+            }
+
+            self.stats.expanded += 1;
+            for next in (self.successors)(&state) {
+                self.queue.push_back(next);
+                self.stats.generate(self.queue.len());
+            }
+            return Some(state);
+        }
+        None
+    }
+}
+
+/// Visits every state reachable from `start` breadth-first.
+pub fn traverse<S, I, F, P>(start: S, successors: F, prune: P) -> Traversal<S, F, P>
+where
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    P: Prune<S>,
+{
+    Traversal {
+        queue: VecDeque::from([start]),
+        successors,
+        prune,
+        stats: Stats::default(),
+    }
+}
+
+/// A state waiting to be expanded, ordered by its bound alone.
+struct Candidate<S, V> {
+    bound: V,
+    state: S,
+}
+
+impl<S, V: Ord> PartialEq for Candidate<S, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bound == other.bound
+    }
+}
+
+impl<S, V: Ord> Eq for Candidate<S, V> {}
+
+impl<S, V: Ord> PartialOrd for Candidate<S, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, V: Ord> Ord for Candidate<S, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound.cmp(&other.bound)
+    }
+}
+
+/// Finds the reachable state with the highest `value`, expanding the most
+/// promising states first.
+///
+/// `bound` must never underestimate the value of a state or anything reachable
+/// from it. States whose bound can't beat the best value found so far are
+/// discarded, and the search stops once no remaining state can.
+pub fn branch_and_bound<S, V, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut value: impl FnMut(&S) -> V,
+    mut bound: impl FnMut(&S) -> V,
+    mut prune: impl Prune<S>,
+) -> Best<S, V>
+where
+    V: Copy + Ord,
+    I: IntoIterator<Item = S>,
+{
+    let mut stats = Stats::default();
+    let mut best: Option<(S, V)> = None;
+    let mut heap = BinaryHeap::from([Candidate {
+        bound: bound(&start),
+        state: start,
+    }]);
+
+    while let Some(Candidate {
+        bound: limit,
+        state,
+    }) = heap.pop()
+    {
+        if best.as_ref().is_some_and(|(_, best)| limit <= *best) || cancel::is_cancelled() {
+            break;
+        }
+        if !prune.admit(&state) {
+            continue;
+        }
+
+        stats.expanded += 1;
+        let state_value = value(&state);
+        let improved = best.as_ref().is_none_or(|(_, best)| state_value > *best);
+        let best_value = if improved {
+            state_value
+        } else {
+            best.as_ref().unwrap().1
+        };
+
+        for next in successors(&state) {
+            let next_bound = bound(&next);
+            if next_bound > best_value {
+                heap.push(Candidate {
+                    bound: next_bound,
+                    state: next,
+                });
+                stats.generate(heap.len());
+            }
+        }
+
+        if improved {
+            best = Some((state, state_value));
+        }
+    }
+
+    Best { best, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{astar, bfs, branch_and_bound, dijkstra, traverse, Dominance, NoPrune};
+
+    const MAZE: [&str; 4] = ["..#.", ".##.", "....", "#.#."];
+
+    fn open(x: i32, y: i32) -> bool {
+        (0..4).contains(&x)
+            && (0..4).contains(&y)
+            && MAZE[y as usize].as_bytes()[x as usize] == b'.'
+    }
+
+    fn neighbors(&(x, y): &(i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| open(x, y))
+    }
+
+    #[test]
+    fn test_bfs() {
+        let outcome = bfs((0, 0), neighbors, |&p| p == (3, 0));
+        let path = outcome.path.unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states.len(), 8);
+        assert_eq!(path.states[0], (0, 0));
+        assert_eq!(path.states[7], (3, 0));
+
+        assert!(bfs((0, 0), neighbors, |&p| p == (2, 0)).path.is_none());
+    }
+
+    #[test]
+    fn test_weighted() {
+        // Moving down costs 5 and every other step costs 1
+        let successors = |p: &(i32, i32)| {
+            let p = *p;
+            neighbors(&p).map(move |n| (n, if n.1 > p.1 { 5 } else { 1 }))
+        };
+        let goal = |p: &(i32, i32)| *p == (3, 3);
+        let heuristic = |&(x, y): &(i32, i32)| (3 - x) + (3 - y);
+
+        let slow = dijkstra((0, 0), successors, goal);
+        let fast = astar((0, 0), successors, heuristic, goal);
+        assert_eq!(slow.path.as_ref().unwrap().cost, 18);
+        assert_eq!(fast.path.unwrap().cost, 18);
+        assert!(fast.stats.expanded <= slow.stats.expanded);
+    }
+
+    #[test]
+    fn test_traverse() {
+        let mut states = traverse((0, 0), neighbors, HashSet::new());
+        assert_eq!(states.by_ref().count(), 11);
+        assert_eq!(states.stats().expanded, 11);
+
+        // Counting down from 5 reaches each number once per path without
+        // pruning, and once in total when only the fewest steps are kept
+        let countdown = |&(n, steps): &(u32, u32)| {
+            (1..=2)
+                .filter(move |d| *d <= n)
+                .map(move |d| (n - d, steps + 1))
+        };
+        let all = traverse((5, 0), countdown, NoPrune).count();
+        let fewest_steps = traverse(
+            (5, 0),
+            countdown,
+            Dominance::new(
+                |&(n, steps): &(u32, u32)| (n, steps),
+                |a: &u32, b: &u32| a <= b,
+            ),
+        )
+        .count();
+        assert_eq!(all, 20);
+        assert_eq!(fewest_steps, 6);
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        // 0/1 knapsack with capacity 10
+        const ITEMS: [(u32, u32); 4] = [(5, 10), (4, 40), (6, 30), (3, 50)];
+        let successors = |&(i, weight, value): &(usize, u32, u32)| {
+            let mut next = Vec::new();
+            if let Some(&(w, v)) = ITEMS.get(i) {
+                next.push((i + 1, weight, value));
+                if weight + w <= 10 {
+                    next.push((i + 1, weight + w, value + v));
+                }
+            }
+            next
+        };
+        let bound = |&(i, _, value): &(usize, u32, u32)| {
+            value + ITEMS[i..].iter().map(|(_, v)| v).sum::<u32>()
+        };
+
+        let result = branch_and_bound((0, 0, 0), successors, |s| s.2, bound, NoPrune);
+        assert_eq!(result.best.unwrap().1, 90);
+    }
+}
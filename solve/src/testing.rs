@@ -0,0 +1,213 @@
+//! Checking days against the examples from their puzzle descriptions.
+//!
+//! [`examples!`](crate::examples) declares one `#[test]` per example, each
+//! running the day's real input parser and part functions:
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! mod tests {
+//!     solve::examples! {
+//!         crate::day(),
+//!         answers,
+//!         test_input: "test.input" => (26, 56000011) [row = 10, search_space = 20],
+//!         inline: text "1,2\n" => (3, _),
+//!     }
+//! }
+//! ```
+//!
+//! `answers` checks every input the workspace's answers file has answers
+//! for, solving each with the parameters listed for it there, so answers the
+//! runner also checks are only written down once. The
+//! other examples give their answers inline: paths are relative to the day's
+//! directory, an answer of `_` skips that part, and parameters in brackets
+//! replace the day's defaults.
+
+use crate::{
+    answers::{Answers, DEFAULT_ANSWERS},
+    inputs, Day, Part, Report,
+};
+use std::path::Path;
+
+/// An example input to solve.
+pub enum Example {
+    /// The path of an input file.
+    File(&'static str),
+    Text(&'static str),
+}
+
+/// Solves `example` and checks each part's answer against the expected one,
+/// if given. Panics if anything doesn't match.
+pub fn check(
+    day: &Day,
+    example: Example,
+    overrides: &[(String, String)],
+    part_one: Option<String>,
+    part_two: Option<String>,
+) {
+    for (name, _) in overrides {
+        assert!(
+            day.param_specs().iter().any(|spec| spec.name() == name),
+            "day {} has no parameter named `{name}`",
+            day.number()
+        );
+    }
+    let params = day.params(overrides).unwrap_or_else(|e| panic!("{e:#}"));
+    let report = match example {
+        Example::File(path) => day.solve(Path::new(path), &params, None),
+        Example::Text(text) => day.solve_text(text, &params),
+    }
+    .unwrap_or_else(|e| panic!("{e:#}"));
+    check_report(&report, "", part_one.as_deref(), part_two.as_deref());
+}
+
+/// Solves each input that the answers file in the workspace at `root` has
+/// answers for on `day`, with the parameters it lists for the input, and
+/// checks them. Panics if anything doesn't match, or if there are no answers
+/// for `day` at all.
+pub fn check_answers(day: &Day, root: &str) {
+    let root = Path::new(root);
+    let answers = Answers::load(&root.join(DEFAULT_ANSWERS)).unwrap_or_else(|e| panic!("{e:#}"));
+    let names = answers.inputs(day.number());
+    assert!(
+        !names.is_empty(),
+        "{DEFAULT_ANSWERS} has no answers for day {}",
+        day.number()
+    );
+
+    for name in names {
+        let params = day
+            .params(answers.params(day.number(), name))
+            .unwrap_or_else(|e| panic!("{name}: {e:#}"));
+        let path = inputs::find_in(root, day, name)
+            .unwrap_or_else(|| panic!("day {} has no input named `{name}`", day.number()));
+        let report = day
+            .solve(&path, &params, None)
+            .unwrap_or_else(|e| panic!("{name}: {e:#}"));
+        check_report(
+            &report,
+            &format!("{name} "),
+            answers.get(day.number(), name, Part::One),
+            answers.get(day.number(), name, Part::Two),
+        );
+    }
+}
+
+/// Checks each part's answer against the expected one, if given. Failures
+/// are described starting with `label`.
+fn check_report(
+    report: &Report<String, String>,
+    label: &str,
+    part_one: Option<&str>,
+    part_two: Option<&str>,
+) {
+    for (part, solution, expected) in [
+        ("one", &report.part_one, part_one),
+        ("two", &report.part_two, part_two),
+    ] {
+        if let Some(expected) = expected {
+            if let Some(e) = solution.error() {
+                panic!("{label}part {part} failed: {e:#}");
+            }
+            if let Some(message) = solution.panicked() {
+                panic!("{label}part {part} panicked: {message}");
+            }
+            assert_eq!(
+                solution.result().map(String::as_str),
+                Some(expected),
+                "{label}part {part}"
+            );
+        }
+    }
+}
+
+/// Declares a `#[test]` for each example input of a day. See
+/// [`testing`](crate::testing) for the syntax.
+#[macro_export]
+macro_rules! examples {
+    (@input $path:literal) => {
+        $crate::testing::Example::File(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path))
+    };
+    (@input text $text:literal) => {
+        $crate::testing::Example::Text($text)
+    };
+    (@answer _) => {
+        None
+    };
+    (@answer $answer:tt) => {
+        Some($answer.to_string())
+    };
+    ($day:expr, $(#[$attr:meta])* answers $(, $($examples:tt)*)?) => {
+        #[test]
+        $(#[$attr])*
+        fn answers() {
+            $crate::testing::check_answers(&$day, concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+        }
+
+        $crate::examples!($day, $($($examples)*)?);
+    };
+    (
+        $day:expr,
+        $(
+            $(#[$attr:meta])*
+            $name:ident: $($kind:ident)? $input:literal => ($one:tt, $two:tt)
+            $([$($param:ident = $value:tt),* $(,)?])?
+        ),* $(,)?
+    ) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $name() {
+                $crate::testing::check(
+                    &$day,
+                    $crate::examples!(@input $($kind)? $input),
+                    &[$($((stringify!($param).to_string(), $value.to_string())),*)?],
+                    $crate::examples!(@answer $one),
+                    $crate::examples!(@answer $two),
+                );
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Day, Params};
+
+    fn day() -> Day {
+        Day::with_params(
+            0,
+            |input: &String, _: &Params| input.lines().count(),
+            |input: &String, params: &Params| input.len() * params.get::<usize>("scale"),
+        )
+        .param("scale", 1usize)
+    }
+
+    crate::examples! {
+        day(),
+        text_input: text "ab\ncd\n" => (2, 6),
+        scaled: text "ab\n" => (_, 30) [scale = 10],
+        #[should_panic(expected = "part two")]
+        wrong_answer: text "ab\n" => (1, 4),
+    }
+
+    fn fallible() -> Day {
+        Day::new(
+            0,
+            |input: &String| input.trim().parse::<i32>(),
+            |_: &String| -> anyhow::Result<i32> { anyhow::bail!("no answer") },
+        )
+    }
+
+    crate::examples! {
+        day(),
+        #[should_panic(expected = "answers.txt has no answers for day 0")]
+        answers,
+    }
+
+    crate::examples! {
+        fallible(),
+        fallible_input: text "12\n" => (12, _),
+        #[should_panic(expected = "part two failed: no answer")]
+        failed_part: text "12\n" => (_, 0),
+    }
+}
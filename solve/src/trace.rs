@@ -0,0 +1,242 @@
+//! Lightweight tracing, for following a solver step by step instead of
+//! adding `println!`s when it disagrees with an example.
+//!
+//! Solvers emit events with [`event!`](crate::event), each with a level, a
+//! message and some named fields:
+//!
+//! ```ignore
+//! solve::event!(trace, "cycle", cycle = self.cycle, x = self.x);
+//! ```
+//!
+//! An event's target is the name of the crate it comes from, like `day_10`.
+//! Nothing is written unless the target was enabled with a [`Directive`],
+//! which the runner takes as `--trace day_10`. Until then an event costs a
+//! single atomic load, and its fields aren't even evaluated.
+
+use anyhow::{bail, Error, Result};
+use std::{
+    fmt::{self, Debug, Display, Write as _},
+    io::Write,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex, RwLock,
+    },
+};
+
+/// How detailed an event is. Enabling a level also enables the ones before
+/// it.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// A handful of events per part, like the result of each round.
+    Info = 1,
+    Debug = 2,
+    /// Every step, like each cycle of a machine.
+    Trace = 3,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            x => bail!("unknown trace level `{x}`, expected info, debug or trace"),
+        })
+    }
+}
+
+/// Which events to write, given as `target` or `target=level`. The target
+/// is a crate name like `day_10`, or `all` for every crate, and the level
+/// defaults to [`Level::Trace`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Directive {
+    /// `None` for every target.
+    target: Option<String>,
+    level: Level,
+}
+
+impl Directive {
+    /// The target the directive enables, or `None` if it enables them all.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    fn enables(&self, level: Level, target: &str) -> bool {
+        level <= self.level && self.target.as_deref().is_none_or(|t| t == target)
+    }
+}
+
+impl FromStr for Directive {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (target, level) = match s.split_once('=') {
+            Some((target, level)) => (target, level.parse()?),
+            None => (s, Level::Trace),
+        };
+        let target = match target {
+            "" => bail!("expected a target in `{s}`"),
+            "all" => None,
+            target => Some(target.to_string()),
+        };
+        Ok(Self { target, level })
+    }
+}
+
+struct Tracer {
+    directives: Vec<Directive>,
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+/// The most detailed level any directive enables, or 0 if tracing is off.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static TRACER: RwLock<Option<Tracer>> = RwLock::new(None);
+
+/// Starts writing the events `directives` enable to `sink`, replacing any
+/// earlier setup.
+pub fn init(directives: Vec<Directive>, sink: impl Write + Send + 'static) {
+    let max_level = directives.iter().map(|d| d.level as u8).max().unwrap_or(0);
+    *TRACER.write().unwrap() = Some(Tracer {
+        directives,
+        sink: Mutex::new(Box::new(sink)),
+    });
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+}
+
+/// The crate a module path belongs to.
+fn crate_name(module_path: &str) -> &str {
+    module_path.split("::").next().unwrap_or(module_path)
+}
+
+/// Whether an event at `level` from the module at `module_path` would be
+/// written.
+#[inline]
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) && enabled_slow(level, module_path)
+}
+
+#[cold]
+fn enabled_slow(level: Level, module_path: &str) -> bool {
+    let target = crate_name(module_path);
+    TRACER.read().unwrap().as_ref().is_some_and(|tracer| {
+        tracer
+            .directives
+            .iter()
+            .any(|directive| directive.enables(level, target))
+    })
+}
+
+/// Writes an event as a single line. Use [`event!`](crate::event) rather
+/// than calling this directly.
+pub fn emit(level: Level, module_path: &str, message: &str, fields: &[(&str, &dyn Debug)]) {
+    let mut line = format!("[{} {level}] {message}", crate_name(module_path));
+    for (name, value) in fields {
+        let _ = write!(line, " {name}={value:?}");
+    }
+    line.push('\n');
+
+    if let Some(tracer) = TRACER.read().unwrap().as_ref() {
+        // A broken sink shouldn't take the solver down with it
+        let _ = tracer.sink.lock().unwrap().write_all(line.as_bytes());
+    }
+}
+
+/// Emits a trace event from a solver if its crate has tracing enabled. The
+/// level is one of `info`, `debug` or `trace`, and each field is written
+/// with its `Debug` representation. See [`trace`](crate::trace).
+#[macro_export]
+macro_rules! event {
+    (@level info) => {
+        $crate::trace::Level::Info
+    };
+    (@level debug) => {
+        $crate::trace::Level::Debug
+    };
+    (@level trace) => {
+        $crate::trace::Level::Trace
+    };
+    ($level:ident, $message:expr $(, $field:ident = $value:expr)* $(,)?) => {{
+        let level = $crate::event!(@level $level);
+        if $crate::trace::enabled(level, module_path!()) {
+            $crate::trace::emit(
+                level,
+                module_path!(),
+                $message,
+                &[$((stringify!($field), &$value as &dyn ::std::fmt::Debug)),*],
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{init, Directive, Level};
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_directive() {
+        let directive = "day_10=debug".parse::<Directive>().unwrap();
+        assert_eq!(directive.target(), Some("day_10"));
+        assert!(directive.enables(Level::Info, "day_10"));
+        assert!(!directive.enables(Level::Trace, "day_10"));
+        assert!(!directive.enables(Level::Info, "day_11"));
+
+        let all = "all".parse::<Directive>().unwrap();
+        assert_eq!(all.target(), None);
+        assert!(all.enables(Level::Trace, "day_11"));
+
+        assert!("day_10=loud".parse::<Directive>().is_err());
+        assert!("=debug".parse::<Directive>().is_err());
+    }
+
+    #[test]
+    fn test_event() {
+        let sink = Shared::default();
+        init(vec!["solve=debug".parse().unwrap()], sink.clone());
+
+        let mut evaluated = false;
+        crate::event!(debug, "step", cycle = 3, x = "a");
+        crate::event!(
+            trace,
+            "skipped",
+            seen = {
+                evaluated = true;
+                1
+            }
+        );
+        init(Vec::new(), io::sink());
+
+        assert!(!evaluated);
+        let output = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output, "[solve debug] step cycle=3 x=\"a\"\n");
+    }
+}
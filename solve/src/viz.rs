@@ -0,0 +1,359 @@
+//! Watching spatial simulations play out in the terminal.
+//!
+//! Days that simulate something on a grid draw each step as a [`Frame`] and
+//! hand it to [`show`]:
+//!
+//! ```ignore
+//! viz::show(|| Frame::new().points(&sand, Cell::new('o', Color::Yellow)));
+//! ```
+//!
+//! Nothing is drawn unless visualisation was turned on, which the runner
+//! does with `--visualize`, or frames are being exported as images with
+//! [`export`](crate::export). Until then [`show`] doesn't even build the
+//! frame, so the calls can be left in.
+
+#[cfg(feature = "export")]
+use crate::export;
+use crate::{geom::Vec2, Grid};
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Frames are cropped to this many columns and rows, keeping the top left,
+/// so that large simulations don't wrap.
+const MAX_WIDTH: usize = 200;
+const MAX_HEIGHT: usize = 60;
+
+/// The colour drawn for cells with no colour of their own when exporting.
+#[cfg(feature = "export")]
+const PLAIN: [u8; 3] = [204, 204, 204];
+
+/// The ANSI terminal colours, or an exact colour for terminals that support
+/// 24-bit colour.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The SGR parameters that set this as the foreground colour.
+    fn sgr(self) -> String {
+        match self {
+            Color::Red => "31".to_string(),
+            Color::Green => "32".to_string(),
+            Color::Yellow => "33".to_string(),
+            Color::Blue => "34".to_string(),
+            Color::Magenta => "35".to_string(),
+            Color::Cyan => "36".to_string(),
+            Color::White => "37".to_string(),
+            Color::Gray => "90".to_string(),
+            Color::Rgb(r, g, b) => format!("38;2;{r};{g};{b}"),
+        }
+    }
+
+    /// The colour as red, green and blue components, with the ANSI colours
+    /// as a typical terminal shows them.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Red => [205, 49, 49],
+            Color::Green => [13, 188, 121],
+            Color::Yellow => [229, 229, 16],
+            Color::Blue => [36, 114, 200],
+            Color::Magenta => [188, 63, 188],
+            Color::Cyan => [17, 168, 205],
+            Color::White => [229, 229, 229],
+            Color::Gray => [102, 102, 102],
+            Color::Rgb(r, g, b) => [r, g, b],
+        }
+    }
+}
+
+/// A character drawn at one position of a frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    ch: char,
+    color: Option<Color>,
+}
+
+impl Cell {
+    pub const fn new(ch: char, color: Color) -> Self {
+        Self {
+            ch,
+            color: Some(color),
+        }
+    }
+
+    /// A cell in the terminal's default colour.
+    pub const fn plain(ch: char) -> Self {
+        Self { ch, color: None }
+    }
+
+    /// The colour the cell is exported as, or `None` if it's blank.
+    #[cfg(feature = "export")]
+    pub(crate) fn rgb(self) -> Option<[u8; 3]> {
+        (self.ch != ' ').then(|| self.color.map_or(PLAIN, Color::rgb))
+    }
+}
+
+/// Something that can be drawn at a position in a frame, in the screen
+/// coordinates [`Grid`] and [`geom`](crate::geom) use.
+pub trait Position {
+    fn position(self) -> (i64, i64);
+}
+
+macro_rules! impl_position {
+    ($($t:ty),*) => {
+        $(
+            impl Position for ($t, $t) {
+                fn position(self) -> (i64, i64) {
+                    (self.0 as i64, self.1 as i64)
+                }
+            }
+
+            impl Position for Vec2<$t> {
+                fn position(self) -> (i64, i64) {
+                    (self.x as i64, self.y as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_position!(i32, i64, isize, u32, usize);
+
+impl<P: Position + Copy> Position for &P {
+    fn position(self) -> (i64, i64) {
+        (*self).position()
+    }
+}
+
+/// One picture of a simulation. Positions that nothing was drawn at are
+/// left blank, and the frame grows to fit whatever is drawn.
+#[derive(Clone, Debug, Default)]
+pub struct Frame {
+    cells: HashMap<(i64, i64), Cell>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws `cell` at `position`, over anything drawn there before.
+    pub fn cell(mut self, position: impl Position, cell: Cell) -> Self {
+        self.cells.insert(position.position(), cell);
+        self
+    }
+
+    /// Draws `cell` at each of `points`.
+    pub fn points<P: Position>(mut self, points: impl IntoIterator<Item = P>, cell: Cell) -> Self {
+        for point in points {
+            self.cells.insert(point.position(), cell);
+        }
+        self
+    }
+
+    /// Draws every cell of `grid` that `f` gives a cell for, with the grid's
+    /// top left corner at the origin.
+    pub fn grid<T>(mut self, grid: &Grid<T>, mut f: impl FnMut(&T) -> Option<Cell>) -> Self {
+        for (position, value) in grid.iter() {
+            if let Some(cell) = f(value) {
+                self.cells.insert(position.position(), cell);
+            }
+        }
+        self
+    }
+
+    /// Every cell drawn in the frame.
+    #[cfg(feature = "export")]
+    pub(crate) fn cells(&self) -> impl Iterator<Item = ((i64, i64), Cell)> + '_ {
+        self.cells.iter().map(|(&position, &cell)| (position, cell))
+    }
+
+    /// Text shown beneath the frame, like the step it shows.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Renders the frame as lines of text with ANSI colour codes.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        let positions = self.cells.keys();
+        if let (Some(min_x), Some(min_y)) = (
+            positions.clone().map(|p| p.0).min(),
+            positions.clone().map(|p| p.1).min(),
+        ) {
+            let max_x = positions.clone().map(|p| p.0).max().unwrap();
+            let max_y = positions.map(|p| p.1).max().unwrap();
+            let max_x = max_x.min(min_x + MAX_WIDTH as i64 - 1);
+            let max_y = max_y.min(min_y + MAX_HEIGHT as i64 - 1);
+
+            for y in min_y..=max_y {
+                let mut color = None;
+                let mut line = String::new();
+                for x in min_x..=max_x {
+                    let cell = self.cells.get(&(x, y)).copied().unwrap_or(Cell::plain(' '));
+                    if cell.color != color && cell.ch != ' ' {
+                        match cell.color {
+                            Some(c) => write!(line, "\x1b[{}m", c.sgr()).unwrap(),
+                            None => line.push_str("\x1b[0m"),
+                        }
+                        color = cell.color;
+                    }
+                    line.push(cell.ch);
+                }
+                output.push_str(line.trim_end());
+                if color.is_some() {
+                    output.push_str("\x1b[0m");
+                }
+                output.push('\n');
+            }
+        }
+        if let Some(caption) = &self.caption {
+            output.push_str(caption);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// How frames are played back.
+#[derive(Clone, Copy, Debug)]
+pub struct Playback {
+    /// Frames shown per second, or 0 to show them as fast as they come.
+    pub fps: f64,
+    /// Wait for Enter before moving on from each frame.
+    pub step: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            fps: 30.0,
+            step: false,
+        }
+    }
+}
+
+struct Player {
+    playback: Playback,
+    frames: usize,
+    last_shown: Option<Instant>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PLAYER: Mutex<Option<Player>> = Mutex::new(None);
+
+/// Turns visualisation on, playing frames back as `playback` says.
+pub fn init(playback: Playback) {
+    *PLAYER.lock().unwrap() = Some(Player {
+        playback,
+        frames: 0,
+        last_shown: None,
+    });
+    enable();
+}
+
+/// Makes [`show`] build frames, for when they're only being exported.
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether frames passed to [`show`] are being drawn.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Draws the frame built by `frame` in place of the last one, if
+/// visualisation is on, and records it if frames are being exported.
+pub fn show(frame: impl FnOnce() -> Frame) {
+    if !enabled() {
+        return;
+    }
+    let frame = frame();
+    #[cfg(feature = "export")]
+    export::record(&frame);
+
+    let mut slot = PLAYER.lock().unwrap();
+    let Some(player) = slot.as_mut() else {
+        return;
+    };
+    player.frames += 1;
+
+    if let Some(last_shown) = player.last_shown {
+        if !player.playback.step && player.playback.fps > 0.0 {
+            let interval = Duration::from_secs_f64(1.0 / player.playback.fps);
+            thread::sleep(interval.saturating_sub(last_shown.elapsed()));
+        }
+    }
+
+    // Clear the screen and draw from the top left
+    let mut stdout = io::stdout().lock();
+    let _ = write!(
+        stdout,
+        "\x1b[H\x1b[2J{}frame {}",
+        frame.render(),
+        player.frames
+    );
+    if player.playback.step {
+        let _ = write!(stdout, " (Enter for the next frame, q to stop watching)");
+        let _ = stdout.flush();
+        let mut answer = String::new();
+        let _ = io::stdin().lock().read_line(&mut answer);
+        if answer.trim() == "q" {
+            *slot = None;
+            #[cfg(feature = "export")]
+            if export::is_recording() {
+                return;
+            }
+            ENABLED.store(false, Ordering::Relaxed);
+            return;
+        }
+    } else {
+        let _ = writeln!(stdout);
+        let _ = stdout.flush();
+    }
+    player.last_shown = Some(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, Color, Frame};
+    use crate::{geom::Vec2i, Grid};
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from_vec(3, 2, vec![true, false, true, false, false, true]);
+        let frame = Frame::new()
+            .grid(&grid, |&wall| wall.then_some(Cell::plain('#')))
+            .points(
+                [Vec2i::new(1, 1), Vec2i::new(1, 2)],
+                Cell::new('o', Color::Red),
+            )
+            .caption("step 1");
+        assert_eq!(
+            frame.render(),
+            "# #\n \x1b[31mo\x1b[0m#\n \x1b[31mo\x1b[0m\nstep 1\n"
+        );
+        assert_eq!(Frame::new().render(), "");
+    }
+}
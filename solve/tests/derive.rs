@@ -0,0 +1,7 @@
+//! Compile-fail tests for `#[derive(FromStr)]` formats.
+
+#[test]
+fn invalid_formats() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
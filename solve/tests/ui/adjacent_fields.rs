@@ -0,0 +1,10 @@
+use solve::FromStr;
+
+#[derive(FromStr)]
+#[format("{x}{y}")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use solve::FromStr;
+
+#[derive(FromStr)]
+#[format("move {amount from {from}")]
+struct Instruction {
+    amount: usize,
+    from: usize,
+}
+
+fn main() {}
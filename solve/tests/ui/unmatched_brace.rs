@@ -0,0 +1,9 @@
+use solve::FromStr;
+
+#[derive(FromStr)]
+#[format("{x}, y}")]
+struct Point {
+    x: i32,
+}
+
+fn main() {}
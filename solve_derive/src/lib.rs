@@ -0,0 +1,264 @@
+//! Derive macros for the `solve` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Splits a template like `"move {amount} from {from}"` into literal text and
+/// field placeholders. `{{` and `}}` are literal braces.
+fn parse_template(template: &LitStr) -> Result<Vec<Segment>> {
+    let text = template.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        // A placeholder can't contain another, so the first was never closed
+                        Some('{') | None => {
+                            return Err(Error::new(template.span(), "unclosed `{` in format"))
+                        }
+                        Some(c) => name.push(c),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if matches!(segments.last(), Some(Segment::Field(_))) {
+                    return Err(Error::new(
+                        template.span(),
+                        "fields in a format must be separated by literal text",
+                    ));
+                }
+                segments.push(Segment::Field(name.trim().to_string()));
+            }
+            '}' => return Err(Error::new(template.span(), "unmatched `}` in format")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+fn find_template(input: &DeriveInput) -> Result<LitStr> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("format"))
+        .ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                "expected a `#[format(\"...\")]` attribute describing the input format",
+            )
+        })?;
+    attr.parse_args()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "FromStr can only be derived for structs",
+        ));
+    };
+    let template = find_template(&input)?;
+    let segments = parse_template(&template)?;
+
+    // Map each placeholder name to its field's binding and type
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => (ident.to_string(), ident.clone(), &field.ty),
+            None => (i.to_string(), format_ident!("__field{}", i), &field.ty),
+        })
+        .collect::<Vec<_>>();
+
+    for segment in segments.iter() {
+        if let Segment::Field(name) = segment {
+            if !fields.iter().any(|(field, _, _)| field == name) {
+                return Err(Error::new(
+                    template.span(),
+                    format!("format refers to unknown field `{name}`"),
+                ));
+            }
+        }
+    }
+    for (name, _, _) in fields.iter() {
+        let uses = segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Field(field) if field == name))
+            .count();
+        if uses != 1 {
+            return Err(Error::new(
+                template.span(),
+                format!("field `{name}` must appear exactly once in the format, found {uses}"),
+            ));
+        }
+    }
+
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => {
+                let message = format!("expected {literal:?}");
+                steps.push(quote! {
+                    if !s[offset..].starts_with(#literal) {
+                        return Err(::solve::ParseError::at_offset(#message, s, offset, #literal.len()));
+                    }
+                    offset += #literal.len();
+                });
+            }
+            Segment::Field(name) => {
+                let (_, binding, ty) = fields.iter().find(|(field, _, _)| field == name).unwrap();
+                // A field runs until the next literal, or to the end of the input
+                let end = match segments.get(i + 1) {
+                    Some(Segment::Literal(next)) => {
+                        let message = format!("expected {next:?} after `{name}`");
+                        quote! {
+                            match s[offset..].find(#next) {
+                                Some(len) => offset + len,
+                                None => {
+                                    return Err(::solve::ParseError::at_offset(#message, s, s.len(), 0));
+                                }
+                            }
+                        }
+                    }
+                    _ => quote! { s.len() },
+                };
+                let message = format!("invalid `{name}`: ");
+                steps.push(quote! {
+                    let end = #end;
+                    let #binding = <#ty as ::std::str::FromStr>::from_str(&s[offset..end]).map_err(|e| {
+                        ::solve::ParseError::at_offset(format!("{}{:#}", #message, e), s, offset, end - offset)
+                    })?;
+                    offset = end;
+                });
+            }
+        }
+    }
+
+    let bindings = fields.iter().map(|(_, binding, _)| binding);
+    let construct = match &data.fields {
+        Fields::Named(_) => quote! { Self { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { Self(#(#bindings),*) },
+        Fields::Unit => quote! { Self },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::solve::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                let mut offset = 0;
+                #(#steps)*
+                if offset != s.len() {
+                    return Err(::solve::ParseError::at_offset("unexpected trailing input", s, offset, s.len() - offset));
+                }
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+/// Derives `FromStr` for a struct from a `#[format("...")]` template.
+///
+/// Placeholders name the struct's fields (or tuple indices) and are parsed
+/// with each field's own `FromStr`. A placeholder's text runs up to the next
+/// piece of literal text, so placeholders must be separated by literals.
+///
+/// ```ignore
+/// #[derive(FromStr)]
+/// #[format("move {amount} from {from} to {to}")]
+/// struct Instruction {
+///     amount: usize,
+///     from: usize,
+///     to: usize,
+/// }
+/// ```
+///
+/// Errors are `solve::ParseError`s naming the field or literal that failed and
+/// pointing at its position in the input.
+#[proc_macro_derive(FromStr, attributes(format))]
+pub fn derive_from_str(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_template, Segment};
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    fn parse(template: &str) -> Result<Vec<Segment>, String> {
+        parse_template(&LitStr::new(template, Span::call_site())).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_parse_template() {
+        use Segment::{Field, Literal};
+
+        assert_eq!(
+            parse("move {amount} from { from } to {to}").unwrap(),
+            [
+                Literal("move ".to_string()),
+                Field("amount".to_string()),
+                Literal(" from ".to_string()),
+                Field("from".to_string()),
+                Literal(" to ".to_string()),
+                Field("to".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse("{{{0}}}").unwrap(),
+            [
+                Literal("{".to_string()),
+                Field("0".to_string()),
+                Literal("}".to_string()),
+            ]
+        );
+        assert_eq!(parse("").unwrap(), []);
+    }
+
+    #[test]
+    fn test_parse_template_errors() {
+        assert_eq!(parse("move {amount").unwrap_err(), "unclosed `{` in format");
+        assert_eq!(
+            parse("move {amount from {from}").unwrap_err(),
+            "unclosed `{` in format"
+        );
+        assert_eq!(parse("{x}, y}").unwrap_err(), "unmatched `}` in format");
+        assert_eq!(
+            parse("{x}{y}").unwrap_err(),
+            "fields in a format must be separated by literal text"
+        );
+    }
+}